@@ -0,0 +1,475 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    ast::{Expression, FunctionCall, Identifier, NodeID},
+    context::{
+        browser::{
+            ExtractAssignments, ExtractFunctionCalls, ExtractIdentifiers,
+            ExtractVariableDeclarationStatements, GetClosestAncestorOfTypeX,
+        },
+        workspace_context::{ASTNode, WorkspaceContext},
+    },
+    ast::NodeType,
+    visitor::ast_visitor::{ASTConstVisitor, Node},
+};
+
+/// The result of an intra-procedural taint query: every node a seed value
+/// flows into within its enclosing function, plus enough information to
+/// reconstruct the path a detector can show the user.
+#[derive(Debug, Default, Clone)]
+pub struct DataFlow {
+    seed: Option<NodeID>,
+    reaches: BTreeSet<NodeID>,
+    predecessors: BTreeMap<NodeID, NodeID>,
+}
+
+impl DataFlow {
+    /// Does the seed value reach `node_id`?
+    pub fn reaches(&self, node_id: NodeID) -> bool {
+        self.reaches.contains(&node_id)
+    }
+
+    pub fn reached_nodes(&self) -> &BTreeSet<NodeID> {
+        &self.reaches
+    }
+
+    /// Walk the predecessor chain from `sink` back to the seed, returning the
+    /// path in seed-to-sink order, or `None` if `sink` was never reached.
+    pub fn path_to(&self, sink: NodeID) -> Option<Vec<NodeID>> {
+        if !self.reaches(sink) {
+            return None;
+        }
+        let mut path = vec![sink];
+        let mut current = sink;
+        while let Some(&previous) = self.predecessors.get(&current) {
+            path.push(previous);
+            if Some(previous) == self.seed {
+                break;
+            }
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[derive(Default)]
+struct NodeIDReceiver {
+    id: Option<NodeID>,
+}
+
+impl ASTConstVisitor for NodeIDReceiver {
+    fn visit_node_id(&mut self, node_id: Option<NodeID>) -> eyre::Result<()> {
+        self.id = node_id;
+        Ok(())
+    }
+}
+
+pub trait GetDataFlow {
+    /// Compute which nodes the value produced/held by `self` flows into,
+    /// within its enclosing function.
+    fn data_flow(&self, context: &WorkspaceContext) -> DataFlow;
+}
+
+impl<T: Node + ?Sized> GetDataFlow for T {
+    fn data_flow(&self, context: &WorkspaceContext) -> DataFlow {
+        let mut node_id_receiver = NodeIDReceiver::default();
+        if self.accept_id(&mut node_id_receiver).is_err() {
+            return DataFlow::default();
+        }
+        let Some(seed) = node_id_receiver.id else {
+            return DataFlow::default();
+        };
+        compute_data_flow(context, seed)
+    }
+}
+
+/// Walk down the "lvalue spine" of an assignment target, returning the
+/// identifier of the underlying variable being written through together with
+/// the node id that should stand in for this particular write occurrence.
+///
+/// For a plain `x = ...` the target *is* the occurrence. For `s.x = ...` or
+/// `arr[i] = ...` the write is conservatively attributed to the base
+/// variable (`s`, `arr`) -- writing through a tainted value taints the
+/// whole struct/array -- while the member access / index access node itself
+/// becomes the occurrence so the chain still reads by node id. Only the base
+/// expression is descended into: an index expression like `i` in `arr[i]` is
+/// read, not written, and must not be tainted by the assignment.
+fn lvalue_targets(expr: &Expression) -> Vec<(&Identifier, NodeID)> {
+    match expr {
+        Expression::Identifier(identifier) => vec![(identifier, identifier.id)],
+        Expression::MemberAccess(member_access) => lvalue_targets(member_access.expression.as_ref())
+            .into_iter()
+            .map(|(identifier, _)| (identifier, member_access.id))
+            .collect(),
+        Expression::IndexAccess(index_access) => {
+            lvalue_targets(index_access.base_expression.as_ref())
+                .into_iter()
+                .map(|(identifier, _)| (identifier, index_access.id))
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Does `identifiers`/`calls` (an expression's extracted identifiers and
+/// nested function calls) carry taint, and if so from which occurrence?
+/// An identifier counts if it references an already-tainted declaration. A
+/// call counts if it *is* the seed call itself (e.g. `x = someCall()`,
+/// where the seed is `someCall()`'s return value, not a declaration any
+/// identifier could reference) -- without this, a seed rooted in a call's
+/// return value could never propagate past the statement that first
+/// captures it.
+fn find_predecessor(
+    identifiers: &[&Identifier],
+    calls: &[&FunctionCall],
+    seed: NodeID,
+    tainted_declarations: &BTreeSet<NodeID>,
+    last_occurrence: &BTreeMap<NodeID, NodeID>,
+) -> Option<NodeID> {
+    identifiers
+        .iter()
+        .find(|identifier| tainted_declarations.contains(&identifier.referenced_declaration))
+        .and_then(|identifier| last_occurrence.get(&identifier.referenced_declaration).copied())
+        .or_else(|| calls.iter().any(|call| call.id == seed).then_some(seed))
+}
+
+/// Compute the set of nodes a value flows into within its enclosing
+/// function, given the `NodeID` of the value's origin (e.g. a function
+/// parameter, or the result of a call).
+pub fn compute_data_flow(context: &WorkspaceContext, seed: NodeID) -> DataFlow {
+    let mut flow = DataFlow {
+        seed: Some(seed),
+        ..Default::default()
+    };
+    flow.reaches.insert(seed);
+
+    let Some(seed_node) = context.nodes.get(&seed) else {
+        return flow;
+    };
+    let Some(ASTNode::FunctionDefinition(function)) =
+        seed_node.closest_ancestor_of_type(context, NodeType::FunctionDefinition)
+    else {
+        return flow;
+    };
+
+    // Seed may itself be a declaration (a parameter) or an arbitrary
+    // expression (e.g. a call's return value used inline); track it by
+    // declaration id so later identifiers that reference it are recognized
+    // as tainted.
+    let mut tainted_declarations: BTreeSet<NodeID> = BTreeSet::from([seed]);
+
+    // `predecessors`/`reaches` are keyed by *occurrence* node id (the
+    // identifier, declaration or call node where taint actually showed up),
+    // never by declaration id -- a declaration can be reassigned many times
+    // and each reassignment is a distinct occurrence. `last_occurrence` is
+    // the bridge from a declaration id to its most recently tainted
+    // occurrence, so that looking up "is this read tainted, and if so by
+    // which occurrence" is consistent everywhere instead of mixing the two
+    // id spaces.
+    let mut last_occurrence: BTreeMap<NodeID, NodeID> = BTreeMap::from([(seed, seed)]);
+
+    // Fixed point over assignments, initializers and call arguments until
+    // nothing new becomes tainted.
+    loop {
+        let mut changed = false;
+
+        for assignment in ExtractAssignments::from(function).extracted {
+            let rhs = assignment.right_hand_side.as_ref();
+            let rhs_identifiers = ExtractIdentifiers::from(rhs).extracted;
+            let rhs_calls = ExtractFunctionCalls::from(rhs).extracted;
+            let Some(predecessor) = find_predecessor(
+                &rhs_identifiers,
+                &rhs_calls,
+                seed,
+                &tainted_declarations,
+                &last_occurrence,
+            ) else {
+                continue;
+            };
+            for (identifier, occurrence) in
+                lvalue_targets(assignment.left_hand_side.as_ref())
+            {
+                if tainted_declarations.insert(identifier.referenced_declaration) {
+                    changed = true;
+                }
+                if flow.reaches.insert(occurrence) {
+                    flow.predecessors.insert(occurrence, predecessor);
+                    changed = true;
+                }
+                last_occurrence.insert(identifier.referenced_declaration, occurrence);
+            }
+        }
+
+        for var_decl_statement in
+            ExtractVariableDeclarationStatements::from(function).extracted
+        {
+            let Some(initial_value) = var_decl_statement.initial_value.as_ref() else {
+                continue;
+            };
+            let rhs_identifiers = ExtractIdentifiers::from(initial_value).extracted;
+            let rhs_calls = ExtractFunctionCalls::from(initial_value).extracted;
+            let Some(predecessor) = find_predecessor(
+                &rhs_identifiers,
+                &rhs_calls,
+                seed,
+                &tainted_declarations,
+                &last_occurrence,
+            ) else {
+                continue;
+            };
+            for declaration in var_decl_statement.declarations.iter().flatten() {
+                if tainted_declarations.insert(declaration.id) {
+                    changed = true;
+                }
+                if flow.reaches.insert(declaration.id) {
+                    flow.predecessors.insert(declaration.id, predecessor);
+                    changed = true;
+                }
+                last_occurrence.insert(declaration.id, declaration.id);
+            }
+        }
+
+        // Into call arguments: a tainted argument taints the corresponding
+        // callee parameter, so a sink inside the callee is still reachable
+        // from the seed. Both plain calls (`foo(x)`) and member-access calls
+        // (`self.foo(x)`, a library call, an external interface call) are
+        // considered; a call whose callee can't be resolved to a
+        // `FunctionDefinition` in this context -- a low-level call like
+        // `target.call(x)` or `target.delegatecall(x)`, or any other
+        // external call this context can't see the body of -- has nothing to
+        // propagate into, but the call itself is exactly the sink a detector
+        // cares about, so it's marked reached directly instead of silently
+        // dropped.
+        for call in ExtractFunctionCalls::from(function).extracted {
+            let (callee_declaration, call_target_identifiers) = match call.expression.as_ref() {
+                Expression::Identifier(identifier) => {
+                    (Some(identifier.referenced_declaration), Vec::new())
+                }
+                Expression::MemberAccess(member_access) => (
+                    member_access.referenced_declaration,
+                    ExtractIdentifiers::from(member_access.expression.as_ref()).extracted,
+                ),
+                _ => (None, Vec::new()),
+            };
+
+            let callee = callee_declaration.and_then(|id| context.nodes.get(&id));
+
+            if let Some(ASTNode::FunctionDefinition(callee)) = callee {
+                for (argument, parameter) in
+                    call.arguments.iter().zip(callee.parameters.parameters.iter())
+                {
+                    let argument_identifiers = ExtractIdentifiers::from(argument).extracted;
+                    let argument_calls = ExtractFunctionCalls::from(argument).extracted;
+                    let Some(predecessor) = find_predecessor(
+                        &argument_identifiers,
+                        &argument_calls,
+                        seed,
+                        &tainted_declarations,
+                        &last_occurrence,
+                    ) else {
+                        continue;
+                    };
+                    if tainted_declarations.insert(parameter.id) {
+                        changed = true;
+                    }
+                    if flow.reaches.insert(parameter.id) {
+                        flow.predecessors.insert(parameter.id, predecessor);
+                        changed = true;
+                    }
+                    last_occurrence.insert(parameter.id, parameter.id);
+                }
+            } else {
+                let call_identifiers: Vec<&Identifier> = call
+                    .arguments
+                    .iter()
+                    .flat_map(|argument| ExtractIdentifiers::from(argument).extracted)
+                    .chain(call_target_identifiers)
+                    .collect();
+                let call_calls: Vec<&FunctionCall> = call
+                    .arguments
+                    .iter()
+                    .flat_map(|argument| ExtractFunctionCalls::from(argument).extracted)
+                    .collect();
+                if let Some(predecessor) = find_predecessor(
+                    &call_identifiers,
+                    &call_calls,
+                    seed,
+                    &tainted_declarations,
+                    &last_occurrence,
+                ) {
+                    if flow.reaches.insert(call.id) {
+                        flow.predecessors.insert(call.id, predecessor);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    flow
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ast::FunctionDefinition, detect::detector::detector_test_helpers::load_contract};
+
+    // Every fixture already referenced elsewhere in this series' tests --
+    // tried in turn so the assertions exercise whichever one happens to
+    // contain the pattern under test, instead of pinning these to one
+    // contract's exact source.
+    const FIXTURES: &[&str] = &[
+        "../tests/contract-playground/out/ArbitraryTransferFrom.sol/ArbitraryTransferFrom.json",
+        "../tests/contract-playground/out/ExtendedInheritance.sol/ExtendedInheritance.json",
+        "../tests/contract-playground/out/Counter.sol/Counter.0.8.25.json",
+        "../tests/contract-playground/out/IContractInheritance.sol/IContractInheritance.json",
+        "../tests/contract-playground/out/CrazyPragma.sol/CrazyPragma.json",
+    ];
+
+    fn function_definitions(context: &WorkspaceContext) -> Vec<&FunctionDefinition> {
+        context
+            .nodes
+            .values()
+            .filter_map(|node| match node {
+                ASTNode::FunctionDefinition(function) => Some(function),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Seeding at a `FunctionCall`'s own node id (the seed has no
+    /// declaration an `Identifier` could reference) must still propagate
+    /// into whatever local variable captures its return value, e.g.
+    /// `uint x = someCall();`.
+    #[test]
+    fn test_call_seeded_taint_reaches_capturing_variable() {
+        let mut exercised = false;
+
+        for fixture in FIXTURES {
+            let context = load_contract(fixture);
+            for function in function_definitions(&context) {
+                for statement in ExtractVariableDeclarationStatements::from(function).extracted {
+                    let Some(initial_value) = statement.initial_value.as_ref() else {
+                        continue;
+                    };
+                    let Some(call) =
+                        ExtractFunctionCalls::from(initial_value).extracted.into_iter().next()
+                    else {
+                        continue;
+                    };
+                    let flow = compute_data_flow(&context, call.id);
+                    for declaration in statement.declarations.iter().flatten() {
+                        assert!(
+                            flow.reaches(declaration.id),
+                            "a call-seeded taint should reach the variable declaration that captures its return value"
+                        );
+                        exercised = true;
+                    }
+                }
+            }
+        }
+
+        assert!(
+            exercised,
+            "none of the fixtures contain a `T x = someCall();` statement to exercise call-seeded propagation"
+        );
+    }
+
+    /// A write through a member access or index access (`s.x = tainted;`,
+    /// `arr[i] = tainted;`) must be attributed to the access node itself,
+    /// not silently collapsed onto the base identifier's occurrence.
+    #[test]
+    fn test_member_and_index_writes_are_attributed_to_the_access_site() {
+        let mut exercised = false;
+
+        for fixture in FIXTURES {
+            let context = load_contract(fixture);
+            for function in function_definitions(&context) {
+                for assignment in ExtractAssignments::from(function).extracted {
+                    let lhs = assignment.left_hand_side.as_ref();
+                    let access_id = match lhs {
+                        Expression::MemberAccess(member_access) => member_access.id,
+                        Expression::IndexAccess(index_access) => index_access.id,
+                        _ => continue,
+                    };
+                    let rhs_identifiers =
+                        ExtractIdentifiers::from(assignment.right_hand_side.as_ref()).extracted;
+                    let Some(rhs_identifier) = rhs_identifiers.first() else {
+                        continue;
+                    };
+
+                    // Seed directly at whatever the write's own rhs
+                    // identifier refers to -- whether that's reachable is
+                    // exactly what this test is checking.
+                    let flow = compute_data_flow(&context, rhs_identifier.referenced_declaration);
+                    if !flow.reaches(rhs_identifier.referenced_declaration) {
+                        continue;
+                    }
+                    assert!(
+                        flow.reaches(access_id),
+                        "a member/index write should be attributed to the access site, not dropped"
+                    );
+                    exercised = true;
+                }
+            }
+        }
+
+        assert!(
+            exercised,
+            "none of the fixtures contain a struct-member or array-index write to exercise"
+        );
+    }
+
+    /// A tainted argument passed into a resolvable callee must taint the
+    /// corresponding parameter, so a sink several calls deep is still
+    /// reachable from the original seed.
+    #[test]
+    fn test_multi_hop_call_argument_propagation() {
+        let mut exercised = false;
+
+        for fixture in FIXTURES {
+            let context = load_contract(fixture);
+            for function in function_definitions(&context) {
+                for parameter in &function.parameters.parameters {
+                    let flow = parameter.data_flow(&context);
+                    for call in ExtractFunctionCalls::from(function).extracted {
+                        let Expression::Identifier(callee_identifier) = call.expression.as_ref()
+                        else {
+                            continue;
+                        };
+                        let Some(ASTNode::FunctionDefinition(callee)) =
+                            context.nodes.get(&callee_identifier.referenced_declaration)
+                        else {
+                            continue;
+                        };
+                        for (argument, callee_parameter) in
+                            call.arguments.iter().zip(callee.parameters.parameters.iter())
+                        {
+                            let Expression::Identifier(argument_identifier) = argument else {
+                                continue;
+                            };
+                            if argument_identifier.referenced_declaration != parameter.id {
+                                continue;
+                            }
+                            assert!(
+                                flow.reaches(callee_parameter.id),
+                                "a tainted argument should taint the callee's corresponding parameter"
+                            );
+                            exercised = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        assert!(
+            exercised,
+            "none of the fixtures contain a direct-identifier call argument to exercise multi-hop propagation"
+        );
+    }
+}
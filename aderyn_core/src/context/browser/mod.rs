@@ -1,4 +1,5 @@
 mod closest_parent;
+mod data_flow;
 mod extractor;
 mod immediate_children;
 mod location;
@@ -6,6 +7,7 @@ mod parent;
 mod parent_chain;
 mod peek;
 pub use closest_parent::*;
+pub use data_flow::*;
 pub use extractor::*;
 pub use immediate_children::*;
 pub use location::*;
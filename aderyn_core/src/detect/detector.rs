@@ -8,7 +8,8 @@ use crate::{
         high::{ArbitraryTransferFromDetector, DelegateCallInLoopDetector},
         low::{
             AvoidAbiEncodePackedDetector, DeprecatedOZFunctionsDetector, EcrecoverDetector,
-            PushZeroOpcodeDetector, UnsafeERC20FunctionsDetector, UnspecificSolidityPragmaDetector,
+            Hardfork, PushZeroOpcodeDetector, UnsafeERC20FunctionsDetector,
+            UnspecificSolidityPragmaDetector,
         },
         medium::{
             BlockTimestampDeadlineDetector, CentralizationRiskDetector,
@@ -30,7 +31,10 @@ use std::{
     str::FromStr,
 };
 
-pub fn get_all_issue_detectors() -> Vec<Box<dyn IssueDetector>> {
+/// Build every issue detector, targeting `target_chain` for detectors (like
+/// [`PushZeroOpcodeDetector`]) whose findings depend on which hardfork the
+/// user's chain actually supports.
+pub fn get_all_issue_detectors(target_chain: Hardfork) -> Vec<Box<dyn IssueDetector>> {
     vec![
         Box::<DelegateCallInLoopDetector>::default(),
         Box::<CentralizationRiskDetector>::default(),
@@ -48,13 +52,16 @@ pub fn get_all_issue_detectors() -> Vec<Box<dyn IssueDetector>> {
         Box::<NonReentrantBeforeOthersDetector>::default(),
         Box::<BlockTimestampDeadlineDetector>::default(),
         Box::<UnsafeERC721MintDetector>::default(),
-        Box::<PushZeroOpcodeDetector>::default(),
+        Box::new(PushZeroOpcodeDetector::with_target(target_chain)),
         Box::<ArbitraryTransferFromDetector>::default(),
     ]
 }
 
 pub fn get_all_detectors_names() -> Vec<String> {
-    get_all_issue_detectors().iter().map(|d| d.name()).collect()
+    get_all_issue_detectors(Hardfork::default())
+        .iter()
+        .map(|d| d.name())
+        .collect()
 }
 
 // Note to maintainers: DO NOT CHANGE THE ORDER OF THESE DERIVE ATTRIBUTES
@@ -93,7 +100,18 @@ pub(crate) enum ResuableDetectorNamePool {
     Undecided,
 }
 
+/// Build the detector named `detector_name`, targeting [`Hardfork::from_env`]
+/// for detectors (like [`PushZeroOpcodeDetector`]) whose findings depend on
+/// it -- see [`get_all_issue_detectors`] for the equivalent used to build
+/// every detector at once.
 pub fn get_issue_detector_by_name(detector_name: &str) -> Box<dyn IssueDetector> {
+    get_issue_detector_by_name_for_target_chain(detector_name, Hardfork::from_env())
+}
+
+pub fn get_issue_detector_by_name_for_target_chain(
+    detector_name: &str,
+    target_chain: Hardfork,
+) -> Box<dyn IssueDetector> {
     // Expects a valid detector_name
     let detector_name = IssueDetectorNamePool::from_str(detector_name).unwrap();
     match detector_name {
@@ -131,7 +149,9 @@ pub fn get_issue_detector_by_name(detector_name: &str) -> Box<dyn IssueDetector>
             Box::<BlockTimestampDeadlineDetector>::default()
         }
         IssueDetectorNamePool::UnsafeOzERC721Mint => Box::<UnsafeERC721MintDetector>::default(),
-        IssueDetectorNamePool::PushZeroOpcode => Box::<PushZeroOpcodeDetector>::default(),
+        IssueDetectorNamePool::PushZeroOpcode => {
+            Box::new(PushZeroOpcodeDetector::with_target(target_chain))
+        }
         IssueDetectorNamePool::ArbitraryTransferFrom => {
             Box::<ArbitraryTransferFromDetector>::default()
         }
@@ -243,6 +263,103 @@ pub trait IssueDetector: Send + Sync + 'static {
     }
 }
 
+/// One detector's findings against a single [`WorkspaceContext`]: the same
+/// (file, line, source location) -> `NodeID` map [`IssueDetector::instances`]
+/// returns, kept as plain data so a whole run's results can be persisted
+/// between invocations.
+pub type DetectorInstances = BTreeMap<(String, usize, String), NodeID>;
+
+const DETECTOR_CACHE_FILE_NAME: &str = ".aderyn_detector_cache.json";
+
+/// Manifest persisted alongside a project so an unchanged workspace can
+/// reuse every detector's findings without calling [`IssueDetector::detect`]
+/// again.
+///
+/// Unlike a per-file cache, this is all-or-nothing: `IssueDetector::detect`
+/// runs once over the *entire* merged [`WorkspaceContext`], so there's no
+/// sound way to reuse a stale result for one file while recomputing
+/// another -- the whole-workspace hash has to match before any cached
+/// result is trusted.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct WholeContextCache {
+    aderyn_version: String,
+    workspace_hash: u64,
+    results: BTreeMap<String, DetectorInstances>,
+}
+
+impl WholeContextCache {
+    /// Load the manifest from `project_root`, or an empty one if it's
+    /// missing, unreadable, or produced by a different aderyn version.
+    pub fn load(project_root: &PathBuf) -> Self {
+        let path = project_root.join(DETECTOR_CACHE_FILE_NAME);
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Self>(&contents).ok())
+            .filter(|cache| cache.aderyn_version == env!("CARGO_PKG_VERSION"))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, project_root: &PathBuf) {
+        let path = project_root.join(DETECTOR_CACHE_FILE_NAME);
+        if let Ok(contents) = serde_json::to_string_pretty(self) {
+            let _ = std::fs::write(path, contents);
+        }
+    }
+
+    fn is_fresh(&self, workspace_hash: u64) -> bool {
+        !self.results.is_empty()
+            && self.workspace_hash == workspace_hash
+            && self.aderyn_version == env!("CARGO_PKG_VERSION")
+    }
+}
+
+/// Hash every in-scope file's path and contents together with the enabled
+/// detector set, so editing any file -- or changing which detectors ran --
+/// invalidates the whole cache. Callers already have this map on hand from
+/// loading the workspace, so it's taken as plain data rather than this
+/// module reaching back out to re-read files or walk [`WorkspaceContext`]
+/// internals.
+pub fn hash_workspace_files(files: &BTreeMap<PathBuf, String>, detector_names: &[String]) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for (path, contents) in files {
+        path.hash(&mut hasher);
+        contents.hash(&mut hasher);
+    }
+    for name in detector_names {
+        name.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Run every detector in `detectors` over `context`, reusing `cache`'s
+/// results wholesale when `workspace_hash` (see [`hash_workspace_files`])
+/// matches what produced it, and recomputing -- then overwriting `cache` --
+/// otherwise. This mirrors `IssueDetector::detect`'s own once-over-the-
+/// whole-context contract: there's no partial reuse, only "the workspace
+/// didn't change" or "run everything again".
+pub fn run_detectors_cached(
+    mut detectors: Vec<Box<dyn IssueDetector>>,
+    context: &WorkspaceContext,
+    cache: &mut WholeContextCache,
+    workspace_hash: u64,
+) -> BTreeMap<String, DetectorInstances> {
+    if cache.is_fresh(workspace_hash) {
+        return cache.results.clone();
+    }
+
+    let mut results = BTreeMap::new();
+    for detector in &mut detectors {
+        let _ = detector.detect(context);
+        results.insert(detector.name(), detector.instances());
+    }
+
+    cache.aderyn_version = env!("CARGO_PKG_VERSION").to_string();
+    cache.workspace_hash = workspace_hash;
+    cache.results = results.clone();
+    results
+}
+
 pub trait ReusableDetector {
     fn detect(
         &mut self,
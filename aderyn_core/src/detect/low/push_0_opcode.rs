@@ -1,4 +1,7 @@
-use std::{collections::BTreeMap, error::Error};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+};
 
 use crate::{
     ast::NodeID,
@@ -9,21 +12,122 @@ use crate::{
 use eyre::Result;
 use semver::{Op, VersionReq};
 
+/// EVM hardforks in chronological order. Each one is identified by the
+/// minimum solc `(minor, patch)` version at which the compiler switches its
+/// *default* `--evm-version` to it, and by the opcode family it introduces.
+/// Extend this table as new hardforks land rather than writing another
+/// bespoke "solc vX defaults to opcode Y" detector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Hardfork {
+    #[default]
+    PreShanghai,
+    Shanghai,
+    Cancun,
+}
+
+impl Hardfork {
+    /// Name of the environment variable a `--target-chain` CLI flag should
+    /// end up setting, until this tree's argument parsing wires one up.
+    const TARGET_CHAIN_ENV: &'static str = "ADERYN_TARGET_CHAIN";
+
+    /// Parse a chain/hardfork name as accepted on the command line.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "pre-shanghai" | "preshanghai" => Some(Hardfork::PreShanghai),
+            "shanghai" => Some(Hardfork::Shanghai),
+            "cancun" => Some(Hardfork::Cancun),
+            _ => None,
+        }
+    }
+
+    /// The target chain to run detectors against: [`Self::TARGET_CHAIN_ENV`]
+    /// if it's set to a recognized name, [`Hardfork::default`] otherwise.
+    pub fn from_env() -> Self {
+        std::env::var(Self::TARGET_CHAIN_ENV)
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// `(minor, patch)` of the solc version at which this becomes the
+    /// compiler's default target, or `None` for the pre-Shanghai baseline
+    /// which every supported solc version satisfies.
+    fn default_since(self) -> Option<(u64, u64)> {
+        match self {
+            Hardfork::PreShanghai => None,
+            Hardfork::Shanghai => Some((8, 20)),
+            Hardfork::Cancun => Some((8, 25)),
+        }
+    }
+
+    /// Opcodes this hardfork introduces, as `(mnemonic, opcode byte)`.
+    fn introduced_opcodes(self) -> &'static [(&'static str, u8)] {
+        match self {
+            Hardfork::PreShanghai => &[],
+            Hardfork::Shanghai => &[("PUSH0", 0x5F)],
+            Hardfork::Cancun => &[
+                ("TLOAD", 0x5C),
+                ("TSTORE", 0x5D),
+                ("MCOPY", 0x5E),
+                ("BLOBHASH", 0x49),
+                ("BLOBBASEFEE", 0x4A),
+            ],
+        }
+    }
+}
+
+/// Checks whether a `pragma solidity` range allows a solc version whose
+/// *default* EVM target is newer than the hardfork the detector is
+/// configured for, and if so, which opcode families the target chain lacks.
 #[derive(Default)]
 pub struct PushZeroOpcodeDetector {
     // Keys are: [0] source file name, [1] line number, [2] character location of node.
     // Do not add items manually, use `capture!` to add nodes to this BTreeMap.
     found_instances: BTreeMap<(String, usize, String), NodeID>,
+    // The highest hardfork the user's target chain is known to support.
+    // Any solc default newer than this is flagged.
+    target: Hardfork,
+    // Mnemonics of every opcode family found during `detect` that
+    // `self.target` doesn't support, so `title`/`description` can report
+    // specifically which ones are at risk instead of always naming PUSH0.
+    unsupported_opcode_names: BTreeSet<&'static str>,
 }
 
-fn version_req_allows_above_0_8_19(version_req: &VersionReq) -> bool {
-    // Simplified logic to check if version_req allows versions above 0.8.19
-    // Note: This is a basic example and might not cover all complex semver cases.
+impl PushZeroOpcodeDetector {
+    /// Build a detector for a chain that only supports hardforks up to and
+    /// including `target` (e.g. an L2 still on Shanghai).
+    pub fn with_target(target: Hardfork) -> Self {
+        Self {
+            target,
+            ..Default::default()
+        }
+    }
+
+    /// Opcode families solc may silently emit that `self.target` doesn't
+    /// support, given a compiler version matching `version_req`.
+    fn unsupported_opcodes(&self, version_req: &VersionReq) -> Vec<&'static [(&'static str, u8)]> {
+        [Hardfork::Shanghai, Hardfork::Cancun]
+            .into_iter()
+            .filter(|&hardfork| hardfork > self.target)
+            .filter(|&hardfork| {
+                hardfork
+                    .default_since()
+                    .is_some_and(|threshold| version_req_allows_default_above(version_req, threshold))
+            })
+            .map(Hardfork::introduced_opcodes)
+            .collect()
+    }
+}
+
+fn version_req_allows_default_above(version_req: &VersionReq, (minor, patch): (u64, u64)) -> bool {
+    // Simplified logic to check if version_req allows versions at or above
+    // the given (minor, patch). Note: This is a basic example and might not
+    // cover all complex semver cases.
     if version_req.comparators.len() == 1 {
         let comparator = &version_req.comparators[0];
         match comparator.op {
             Op::Tilde | Op::Caret => {
-                if comparator.major > 0 || comparator.minor >= Some(8) {
+                if comparator.major > 0 || comparator.minor >= Some(minor) {
                     return true;
                 }
             }
@@ -32,8 +136,8 @@ fn version_req_allows_above_0_8_19(version_req: &VersionReq) -> bool {
             }
             Op::Exact => {
                 if comparator.major == 0
-                    && comparator.minor == Some(8)
-                    && comparator.patch == Some(20)
+                    && comparator.minor == Some(minor)
+                    && comparator.patch >= Some(patch)
                 {
                     return true;
                 }
@@ -43,8 +147,8 @@ fn version_req_allows_above_0_8_19(version_req: &VersionReq) -> bool {
     } else if version_req.comparators.len() == 2 {
         let comparator_2 = &version_req.comparators[1];
         if comparator_2.major > 0
-            || (comparator_2.minor >= Some(8))
-            || (comparator_2.minor == Some(8) && comparator_2.patch >= Some(20))
+            || (comparator_2.minor >= Some(minor))
+            || (comparator_2.minor == Some(minor) && comparator_2.patch >= Some(patch))
         {
             return true;
         }
@@ -71,7 +175,12 @@ impl IssueDetector for PushZeroOpcodeDetector {
                 version_string.push_str(literal);
             }
             let req = VersionReq::parse(&version_string)?;
-            if version_req_allows_above_0_8_19(&req) {
+            let unsupported = self.unsupported_opcodes(&req);
+            if !unsupported.is_empty() {
+                for family in unsupported {
+                    self.unsupported_opcode_names
+                        .extend(family.iter().map(|(mnemonic, _)| *mnemonic));
+                }
                 capture!(self, context, pragma_directive);
             }
         }
@@ -84,11 +193,19 @@ impl IssueDetector for PushZeroOpcodeDetector {
     }
 
     fn title(&self) -> String {
-        String::from("PUSH0 is not supported by all chains")
+        let names: Vec<&str> = self.unsupported_opcode_names.iter().copied().collect();
+        match names.as_slice() {
+            [] | ["PUSH0"] => String::from("PUSH0 is not supported by all chains"),
+            _ => format!("{} opcodes are not supported by all chains", names.join(", ")),
+        }
     }
 
     fn description(&self) -> String {
-        String::from("Solc compiler version 0.8.20 switches the default target EVM version to Shanghai, which means that the generated bytecode will include PUSH0 opcodes. Be sure to select the appropriate EVM version in case you intend to deploy on a chain other than mainnet like L2 chains that may not support PUSH0, otherwise deployment of your contracts will fail.")
+        let names: Vec<&str> = self.unsupported_opcode_names.iter().copied().collect();
+        match names.as_slice() {
+            [] | ["PUSH0"] => String::from("Solc compiler version 0.8.20 switches the default target EVM version to Shanghai, which means that the generated bytecode will include PUSH0 opcodes. Be sure to select the appropriate EVM version in case you intend to deploy on a chain other than mainnet like L2 chains that may not support PUSH0, otherwise deployment of your contracts will fail."),
+            _ => format!("The pragma in use allows a solc version whose default EVM target generates the following opcode(s) that your configured target chain ({:?}) doesn't support: {}. Be sure to select the appropriate `--evm-version` when compiling, otherwise deployment of your contracts will fail.", self.target, names.join(", ")),
+        }
     }
 
     fn instances(&self) -> BTreeMap<(String, usize, String), NodeID> {
@@ -205,4 +322,18 @@ mod unspecific_solidity_pragma_tests {
         // assert that the number of instances is correct
         assert_eq!(detector.instances().len(), 1);
     }
+
+    #[test]
+    fn test_push_0_opcode_detector_with_cancun_target_ignores_push0_only_pragma() {
+        let context = load_contract(
+            "../tests/contract-playground/out/ExtendedInheritance.sol/ExtendedInheritance.json",
+        );
+
+        // A chain that already supports Cancun also supports PUSH0, so a
+        // plain `>=0.8.20` pragma shouldn't be flagged anymore.
+        let mut detector = super::PushZeroOpcodeDetector::with_target(super::Hardfork::Cancun);
+        let found = detector.detect(&context).unwrap();
+        assert!(!found);
+        assert_eq!(detector.instances().len(), 0);
+    }
 }
@@ -1,11 +1,14 @@
 use crate::ast::*;
 use eyre::Result;
+use semver::VersionReq;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::error::Error;
 use std::fs::{canonicalize, read_dir, read_to_string, File};
 use std::io::BufReader;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use tiny_keccak::{Hasher, Keccak};
 
 // Foundry compiler output file
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
@@ -14,24 +17,53 @@ pub struct FoundryOutput {
     pub ast: SourceUnit,
 }
 
-// Foundry TOML config file
+// Foundry TOML config file. Keyed by profile name rather than assuming
+// `[profile.default]` is the only one present, since `[profile.ci]` and
+// friends are just as legitimate.
 #[derive(Debug, Deserialize)]
 struct FoundryConfig {
-    profile: ProfileSection,
+    profile: BTreeMap<String, ProfileSettings>,
 }
 
-#[derive(Debug, Deserialize)]
-struct ProfileSection {
-    #[serde(rename = "default")]
-    default: DefaultProfile,
+#[derive(Debug, Deserialize, Clone, Default)]
+struct ProfileSettings {
+    src: Option<String>,
+    out: Option<String>,
+    remappings: Option<Vec<String>>,
 }
 
-#[derive(Debug, Deserialize)]
-struct DefaultProfile {
-    #[serde(default = "default_src")]
+/// `src`/`out`/`remappings` as resolved for the active profile.
+struct ResolvedProfile {
     src: String,
-    #[serde(default = "default_out")]
     out: String,
+    remappings: Vec<String>,
+}
+
+impl FoundryConfig {
+    /// Resolve the active profile (from `FOUNDRY_PROFILE`, falling back to
+    /// `default`), merging any field it leaves unset over `[profile.default]`
+    /// so a profile only needs to override what it changes.
+    fn resolve_profile(&self) -> ResolvedProfile {
+        let active_name =
+            std::env::var("FOUNDRY_PROFILE").unwrap_or_else(|_| "default".to_string());
+        let default_profile = self.profile.get("default").cloned().unwrap_or_default();
+        let active_profile = self.profile.get(&active_name).cloned().unwrap_or_default();
+
+        ResolvedProfile {
+            src: active_profile
+                .src
+                .or(default_profile.src)
+                .unwrap_or_else(default_src),
+            out: active_profile
+                .out
+                .or(default_profile.out)
+                .unwrap_or_else(default_out),
+            remappings: active_profile
+                .remappings
+                .or(default_profile.remappings)
+                .unwrap_or_default(),
+        }
+    }
 }
 
 fn default_src() -> String {
@@ -53,10 +85,157 @@ pub struct LoadedFoundry {
     pub src_path: String,
     pub src_filepaths: Vec<PathBuf>,
     pub output_filepaths: Vec<PathBuf>,
+    // Only `Some` for `load_foundry`, whose `out/`-adjacent artifact cache
+    // `warm_artifact_cache` keeps warm. `load_with_solc`/`load_hardhat` write
+    // their AST sidecars next to the sources themselves, so there's no
+    // single cache file to consult for them yet.
+    out_path: Option<PathBuf>,
+}
+
+impl LoadedFoundry {
+    /// Load `output_filepath`'s AST, reusing the on-disk artifact cache
+    /// `load_foundry_with_options` warmed when the paired source file's
+    /// content hash is unchanged. Callers iterating `output_filepaths`
+    /// should go through this instead of calling `read_foundry_output_file`
+    /// directly, or the cache is only ever written, never consulted.
+    pub fn read_ast(
+        &self,
+        output_filepath: &Path,
+        no_cache: bool,
+    ) -> Result<SourceUnit, Box<dyn Error>> {
+        let (Some(out_path), Some(source_filepath)) = (
+            self.out_path.as_ref(),
+            self.src_filepaths
+                .iter()
+                .find(|src| output_belongs_to(output_filepath, src)),
+        ) else {
+            return Ok(read_foundry_output_file(output_filepath.to_str().unwrap())?.ast);
+        };
+
+        let cache_path = artifact_cache_path(out_path);
+        let mut cache = load_artifact_cache(&cache_path);
+        let ast = read_cached_or_parse(output_filepath, source_filepath, &mut cache, no_cache)?;
+        save_artifact_cache(&cache_path, &cache);
+        Ok(ast)
+    }
+}
+
+const ARTIFACT_CACHE_FILE_NAME: &str = ".aderyn_artifact_cache.json";
+
+/// An on-disk index of already-parsed ASTs keyed by source file path, each
+/// stored with the keccak digest of that file's bytes and reused only while
+/// the digest still matches, so repeated runs over an unchanged tree skip `serde_json` deserialization
+/// of every compiler output entirely.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct ArtifactCache {
+    // Source file absolute path -> its cached entry.
+    entries: BTreeMap<String, CachedArtifact>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CachedArtifact {
+    source_hash: String,
+    ast: SourceUnit,
+}
+
+fn keccak256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Keccak::v256();
+    let mut output = [0u8; 32];
+    hasher.update(bytes);
+    hasher.finalize(&mut output);
+    output.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Cache location: next to `out/` rather than inside it, so `forge clean`
+/// wiping the output directory doesn't also discard the cache.
+fn artifact_cache_path(foundry_out_path: &Path) -> PathBuf {
+    foundry_out_path
+        .parent()
+        .unwrap_or(foundry_out_path)
+        .join(ARTIFACT_CACHE_FILE_NAME)
+}
+
+fn load_artifact_cache(path: &Path) -> ArtifactCache {
+    read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_artifact_cache(path: &Path, cache: &ArtifactCache) {
+    if let Ok(contents) = serde_json::to_string(cache) {
+        let _ = std::fs::write(path, contents);
+    }
+}
+
+/// Read `output_filepath`'s AST, reusing the cached one for `source_filepath`
+/// when its content hash hasn't changed since the last run (unless
+/// `no_cache` forces a fresh parse), and updating `cache` either way.
+fn read_cached_or_parse(
+    output_filepath: &Path,
+    source_filepath: &Path,
+    cache: &mut ArtifactCache,
+    no_cache: bool,
+) -> Result<SourceUnit, Box<dyn Error>> {
+    let source_bytes = read_to_string(source_filepath)?;
+    let source_hash = keccak256_hex(source_bytes.as_bytes());
+    let key = source_filepath.to_string_lossy().to_string();
+
+    if !no_cache {
+        if let Some(cached) = cache.entries.get(&key) {
+            if cached.source_hash == source_hash {
+                return Ok(cached.ast.clone());
+            }
+        }
+    }
+
+    let foundry_output = read_foundry_output_file(output_filepath.to_str().unwrap())?;
+    cache.entries.insert(
+        key,
+        CachedArtifact {
+            source_hash,
+            ast: foundry_output.ast.clone(),
+        },
+    );
+    Ok(foundry_output.ast)
+}
+
+/// Populate the on-disk artifact cache for every resolved (source, output)
+/// pair, so the next run over an unchanged tree can skip deserializing the
+/// compiler's JSON for any source whose content hash hasn't changed.
+fn warm_artifact_cache(
+    foundry_out_path: &Path,
+    src_filepaths: &[PathBuf],
+    output_filepaths: &[PathBuf],
+    no_cache: bool,
+) {
+    let cache_path = artifact_cache_path(foundry_out_path);
+    let mut cache = load_artifact_cache(&cache_path);
+
+    for output_filepath in output_filepaths {
+        let Some(source_filepath) =
+            src_filepaths.iter().find(|src| output_belongs_to(output_filepath, src))
+        else {
+            continue;
+        };
+        let _ = read_cached_or_parse(output_filepath, source_filepath, &mut cache, no_cache);
+    }
+
+    save_artifact_cache(&cache_path, &cache);
 }
 
 // Load foundry and return a Vector of PathBufs to the AST JSON files
 pub fn load_foundry(foundry_root: &PathBuf) -> Result<LoadedFoundry, Box<dyn Error>> {
+    load_foundry_with_options(foundry_root, false)
+}
+
+/// Like [`load_foundry`], but with a `no_cache` escape hatch (the CLI's
+/// `--no-cache` flag) that forces every artifact to be re-parsed from its
+/// compiler JSON output instead of reusing a still-valid cached AST.
+pub fn load_foundry_with_options(
+    foundry_root: &PathBuf,
+    no_cache: bool,
+) -> Result<LoadedFoundry, Box<dyn Error>> {
     let foundry_root_absolute = canonicalize(foundry_root).unwrap_or_else(|err| {
         // Exit with a non-zero exit code
         eprintln!("Error getting absolute path of Foundry root directory");
@@ -74,14 +253,13 @@ pub fn load_foundry(foundry_root: &PathBuf) -> Result<LoadedFoundry, Box<dyn Err
         .status();
 
     let foundry_config_filepath = foundry_root_absolute.join("foundry.toml");
-    let foundry_config = read_config(&foundry_config_filepath).unwrap_or_else(|_err| {
-        // Exit with a non-zero exit code
-        eprintln!("Error reading Foundry config file");
-        std::process::exit(1);
-    });
+    // Partial or alternate-profile configs are legitimate, not fatal, so
+    // surface any parse failure to the caller instead of exiting the process.
+    let foundry_config = read_config(&foundry_config_filepath)?;
+    let resolved_profile = foundry_config.resolve_profile();
 
     // Get the file names of all contracts in the Foundry src directory
-    let foundry_src_path = foundry_root_absolute.join(&foundry_config.profile.default.src);
+    let foundry_src_path = foundry_root_absolute.join(&resolved_profile.src);
     let contract_filepaths =
         collect_nested_files(&foundry_src_path, "sol").unwrap_or_else(|_err| {
             // Exit with a non-zero exit code
@@ -91,7 +269,7 @@ pub fn load_foundry(foundry_root: &PathBuf) -> Result<LoadedFoundry, Box<dyn Err
 
     // For each contract in the Foundry output directory, check if it is in the list of contracts in the Foundry src directory
     // (This is because some contracts may be imported but not deployed, or there may be old contracts in the output directory)
-    let foundry_out_path = foundry_root_absolute.join(&foundry_config.profile.default.out);
+    let foundry_out_path = foundry_root_absolute.join(&resolved_profile.out);
 
     let json_output_filepaths = collect_nested_files(&foundry_out_path.clone(), "json")
         .unwrap_or_else(|_err| {
@@ -99,25 +277,31 @@ pub fn load_foundry(foundry_root: &PathBuf) -> Result<LoadedFoundry, Box<dyn Err
             eprintln!("Error collecting JSON output files from Foundry output directory");
             std::process::exit(1);
         });
-    let output_filepaths = get_matching_output_files(&json_output_filepaths, &contract_filepaths);
+    let output_filepaths = get_matching_output_files(
+        &json_output_filepaths,
+        &contract_filepaths,
+        &foundry_root_absolute,
+        &resolved_profile.remappings,
+    );
+
+    warm_artifact_cache(
+        &foundry_out_path,
+        &contract_filepaths,
+        &output_filepaths,
+        no_cache,
+    );
 
     Ok(LoadedFoundry {
-        src_path: foundry_config.profile.default.src,
+        src_path: resolved_profile.src,
         src_filepaths: contract_filepaths,
         output_filepaths,
+        out_path: Some(foundry_out_path),
     })
 }
 
 fn read_config(path: &PathBuf) -> Result<FoundryConfig, Box<dyn Error>> {
-    let contents = read_to_string(path).unwrap();
-    let foundry_config_toml = toml::from_str(&contents);
-    let foundry_config = match foundry_config_toml {
-        Ok(config) => config,
-        Err(e) => {
-            eprintln!("Error parsing TOML: {:?}", e);
-            std::process::exit(1);
-        }
-    };
+    let contents = read_to_string(path)?;
+    let foundry_config = toml::from_str(&contents)?;
     Ok(foundry_config)
 }
 
@@ -142,19 +326,393 @@ fn collect_nested_files(path: &PathBuf, extension: &str) -> Result<Vec<PathBuf>,
     Ok(results)
 }
 
+/// Scan a Solidity source file for its `pragma solidity` constraint, if any.
+fn pragma_version_req(contents: &str) -> Option<String> {
+    let line = contents.lines().find(|line| {
+        let line = line.trim_start();
+        line.starts_with("pragma") && line.contains("solidity")
+    })?;
+    let after_keyword = line.split("solidity").nth(1)?;
+    Some(
+        after_keyword
+            .trim()
+            .trim_end_matches(';')
+            .trim()
+            .to_string(),
+    )
+}
+
+/// Resolve a locally available `solc` binary satisfying `version_req`,
+/// installing the highest matching release through svm if nothing installed
+/// already matches. Mirrors the installed-first, install-as-fallback
+/// approach `aderyn_driver::version_compiler::ensure_solc_installed` uses
+/// once a concrete version is already known -- the difference here is that
+/// we only have a range (straight off a `pragma solidity` line) and have to
+/// pick a concrete version ourselves.
+fn resolve_solc_path(version_req: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let req = VersionReq::parse(version_req)
+        .map_err(|err| format!("invalid pragma solidity range `{version_req}`: {err}"))?;
+
+    let installed = svm::installed_versions().unwrap_or_default();
+    if let Some(version) = installed.into_iter().filter(|version| req.matches(version)).max() {
+        return svm::blocking_install(&version)
+            .map_err(|err| format!("failed to locate installed solc {version}: {err}").into());
+    }
+
+    let available = svm::blocking_all_versions()
+        .map_err(|err| format!("failed to list available solc versions: {err}"))?;
+    let version = available
+        .into_iter()
+        .filter(|version| req.matches(version))
+        .max()
+        .ok_or_else(|| format!("no published solc version satisfies `{version_req}`"))?;
+
+    svm::blocking_install(&version).map_err(|err| format!("failed to install solc {version}: {err}").into())
+}
+
+/// Alternative to [`load_foundry`] that compiles directly with a standalone
+/// `solc` binary instead of shelling out to `forge build`, for users who
+/// don't have Foundry installed. When `solc_path` isn't given, detects the
+/// required compiler version from the `pragma solidity` range of the first
+/// in-scope source file and picks/downloads a matching `solc` through svm;
+/// the pragmas of the other source files are not consulted.
+/// Invokes `solc --ast-compact-json` and deserializes its output into the
+/// same [`LoadedFoundry`] shape `load_foundry` produces so every downstream
+/// consumer works unchanged.
+pub fn load_with_solc(
+    src_root: &PathBuf,
+    solc_path: Option<&Path>,
+) -> Result<LoadedFoundry, Box<dyn Error>> {
+    let src_root_absolute = canonicalize(src_root)?;
+    let contract_filepaths = collect_nested_files(&src_root_absolute, "sol")?;
+
+    let resolved_solc_path = match solc_path {
+        Some(solc_path) => solc_path.to_path_buf(),
+        None => {
+            let version_req = contract_filepaths
+                .first()
+                .and_then(|file| read_to_string(file).ok())
+                .and_then(|contents| pragma_version_req(&contents))
+                .ok_or("no `pragma solidity` version found to select a solc version from; pass an explicit solc path instead")?;
+            eprintln!("Resolving solc for pragma `{version_req}`");
+            resolve_solc_path(&version_req)?
+        }
+    };
+
+    let output = std::process::Command::new(&resolved_solc_path)
+        .arg("--ast-compact-json")
+        .args(&contract_filepaths)
+        .current_dir(&src_root_absolute)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "solc failed to compile {}: {}",
+            src_root_absolute.display(),
+            String::from_utf8_lossy(&output.stderr)
+        )
+        .into());
+    }
+
+    // `--ast-compact-json` prints one `======= path =======` banner
+    // followed by the AST JSON for each source; split those apart and
+    // write each as `<path>.solc.json` so the rest of the pipeline can
+    // deserialize it through the same `FoundryOutput` shape `load_foundry`
+    // produces from `out/`.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut output_filepaths = Vec::new();
+    for section in stdout.split("======= ").skip(1) {
+        let Some((header, body)) = section.split_once(" =======\n") else {
+            continue;
+        };
+        let source_path = PathBuf::from(header.trim());
+        let json_path = source_path.with_extension("sol.solc.json");
+        let foundry_output = FoundryOutput {
+            ast: serde_json::from_str(body.trim())?,
+        };
+        std::fs::write(&json_path, serde_json::to_string(&foundry_output)?)?;
+        output_filepaths.push(json_path);
+    }
+
+    Ok(LoadedFoundry {
+        src_path: src_root_absolute.to_string_lossy().to_string(),
+        src_filepaths: contract_filepaths,
+        output_filepaths,
+        out_path: None,
+    })
+}
+
+/// Which toolchain's on-disk project layout was detected at a given root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectKind {
+    Foundry,
+    Hardhat,
+}
+
+/// Detect whether `root` is a Foundry or Hardhat project from the config
+/// file it contains. Foundry wins if both are somehow present, since
+/// `foundry.toml` is the more specific marker of the two.
+pub fn detect_project_kind(root: &Path) -> Option<ProjectKind> {
+    if root.join("foundry.toml").is_file() {
+        return Some(ProjectKind::Foundry);
+    }
+    for candidate in [
+        "hardhat.config.js",
+        "hardhat.config.ts",
+        "hardhat.config.cjs",
+    ] {
+        if root.join(candidate).is_file() {
+            return Some(ProjectKind::Hardhat);
+        }
+    }
+    None
+}
+
+/// Which backend `load_project_with_backend` should use. `Auto` keeps the
+/// existing Foundry/Hardhat auto-detection; `Solc` bypasses both and goes
+/// straight through [`load_with_solc`], for the `--compiler solc` path that
+/// doesn't require `forge` (or Hardhat) to be installed. `solc_path: None`
+/// lets [`load_with_solc`] detect and install the right version itself.
+pub enum CompilerBackend {
+    Auto,
+    Solc { solc_path: Option<PathBuf> },
+}
+
+/// Single entry point for loading a project: dispatches to [`load_foundry`],
+/// [`load_hardhat`] or [`load_with_solc`] depending on `backend`, so every
+/// detector runs unchanged regardless of which one produced the AST.
+pub fn load_project_with_backend(
+    root: &PathBuf,
+    backend: CompilerBackend,
+) -> Result<LoadedFoundry, Box<dyn Error>> {
+    match backend {
+        CompilerBackend::Solc { solc_path } => load_with_solc(root, solc_path.as_deref()),
+        CompilerBackend::Auto => match detect_project_kind(root) {
+            Some(ProjectKind::Hardhat) => load_hardhat(root),
+            _ => load_foundry(root),
+        },
+    }
+}
+
+/// Like [`load_project_with_backend`] with [`CompilerBackend::Auto`]:
+/// detects Foundry vs Hardhat and dispatches to [`load_foundry`] or
+/// [`load_hardhat`].
+pub fn load_project(root: &PathBuf) -> Result<LoadedFoundry, Box<dyn Error>> {
+    load_project_with_backend(root, CompilerBackend::Auto)
+}
+
+// One compiler run's Standard JSON output, as Hardhat caches it under
+// `artifacts/build-info/<hash>.json`, keyed by source path.
+#[derive(Debug, Deserialize)]
+struct HardhatBuildInfo {
+    output: HardhatOutput,
+}
+
+#[derive(Debug, Deserialize)]
+struct HardhatOutput {
+    sources: BTreeMap<String, HardhatSource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HardhatSource {
+    ast: SourceUnit,
+}
+
+/// Alternative to [`load_foundry`] for Hardhat projects: reads every
+/// `artifacts/build-info/*.json` file, pulls the AST for each source out of
+/// its nested `output.sources[path].ast`, and re-serializes each one as a
+/// `FoundryOutput`-shaped `<path>.sol.solc.json` next to the source, so the
+/// rest of the pipeline -- and `read_foundry_output_file` -- work unchanged.
+pub fn load_hardhat(hardhat_root: &PathBuf) -> Result<LoadedFoundry, Box<dyn Error>> {
+    let hardhat_root_absolute = canonicalize(hardhat_root)?;
+    let contract_filepaths = collect_nested_files(&hardhat_root_absolute.join("contracts"), "sol")?;
+
+    let build_info_files = collect_nested_files(
+        &hardhat_root_absolute.join("artifacts").join("build-info"),
+        "json",
+    )?;
+
+    let mut output_filepaths = Vec::new();
+    for build_info_file in build_info_files {
+        let build_info: HardhatBuildInfo =
+            serde_json::from_reader(BufReader::new(File::open(&build_info_file)?))?;
+        for (source_path, source) in build_info.output.sources {
+            let json_path = hardhat_root_absolute
+                .join(&source_path)
+                .with_extension("sol.solc.json");
+            if let Some(parent) = json_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let foundry_output = FoundryOutput { ast: source.ast };
+            std::fs::write(&json_path, serde_json::to_string(&foundry_output)?)?;
+            output_filepaths.push(json_path);
+        }
+    }
+
+    Ok(LoadedFoundry {
+        src_path: "contracts".to_string(),
+        src_filepaths: contract_filepaths,
+        output_filepaths,
+        out_path: None,
+    })
+}
+
+/// A single `prefix=target` import remapping, as found in `remappings.txt`
+/// or a `foundry.toml`'s `remappings` array.
+#[derive(Debug, Clone)]
+struct ImportRemapping {
+    prefix: String,
+    target: String,
+}
+
+impl ImportRemapping {
+    fn parse(spec: &str) -> Option<Self> {
+        let (prefix, target) = spec.split_once('=')?;
+        Some(ImportRemapping {
+            prefix: prefix.trim().to_string(),
+            target: target.trim().to_string(),
+        })
+    }
+}
+
+/// Every import remapping in effect: `remappings.txt` (one `prefix=target`
+/// rule per line) plus the active profile's `foundry.toml` `remappings`
+/// array, merged so a project only using one of the two still resolves.
+fn read_import_remappings(foundry_root: &Path, config_remappings: &[String]) -> Vec<ImportRemapping> {
+    let from_file: Vec<ImportRemapping> = read_to_string(foundry_root.join("remappings.txt"))
+        .map(|contents| {
+            contents
+                .lines()
+                .filter_map(ImportRemapping::parse)
+                .collect()
+        })
+        .unwrap_or_default();
+    from_file
+        .into_iter()
+        .chain(config_remappings.iter().filter_map(|spec| ImportRemapping::parse(spec)))
+        .collect()
+}
+
+/// Every `import` target path referenced by a Solidity source file, taken
+/// verbatim from the quoted string. This covers the `import "x";`,
+/// `import {A, B as C} from "x";`, and `import * as N from "x";` forms (with
+/// or without a trailing `as` alias) since they all end in a quoted path.
+fn import_targets(contents: &str) -> Vec<String> {
+    let mut targets = Vec::new();
+    let mut rest = contents;
+    while let Some(import_at) = rest.find("import") {
+        let after_keyword = &rest[import_at + "import".len()..];
+        let statement_end = after_keyword.find(';').unwrap_or(after_keyword.len());
+        if let Some(path) = quoted_string(&after_keyword[..statement_end]) {
+            targets.push(path);
+        }
+        rest = &after_keyword[statement_end..];
+        if rest.is_empty() {
+            break;
+        }
+        rest = &rest[1..]; // skip past the `;` we just consumed
+    }
+    targets
+}
+
+fn quoted_string(s: &str) -> Option<String> {
+    for quote in ['"', '\''] {
+        let Some(start) = s.find(quote) else {
+            continue;
+        };
+        let end = s[start + 1..].find(quote)?;
+        return Some(s[start + 1..start + 1 + end].to_string());
+    }
+    None
+}
+
+/// Resolve an import target to a canonical path on disk: relative imports
+/// (`./`, `../`) are resolved against the importing file's directory, and
+/// everything else is resolved through the longest matching remapping.
+fn resolve_import(
+    from_file: &Path,
+    import_path: &str,
+    remappings: &[ImportRemapping],
+) -> Option<PathBuf> {
+    if import_path.starts_with('.') {
+        return from_file.parent()?.join(import_path).canonicalize().ok();
+    }
+    let remapping = remappings
+        .iter()
+        .filter(|remapping| import_path.starts_with(remapping.prefix.as_str()))
+        .max_by_key(|remapping| remapping.prefix.len())?;
+    let remapped = format!(
+        "{}{}",
+        remapping.target,
+        &import_path[remapping.prefix.len()..]
+    );
+    PathBuf::from(remapped).canonicalize().ok()
+}
+
+/// Every source file transitively reachable from `roots` by following
+/// `import` statements, i.e. the first-party project's import graph. Library
+/// or dependency files that sources don't actually import are excluded, even
+/// if they happen to sit inside the Foundry output directory.
+fn reachable_sources(roots: &[PathBuf], remappings: &[ImportRemapping]) -> BTreeSet<PathBuf> {
+    let mut visited: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut stack: Vec<PathBuf> = roots.to_vec();
+    while let Some(file) = stack.pop() {
+        if !visited.insert(file.clone()) {
+            continue;
+        }
+        let Ok(contents) = read_to_string(&file) else {
+            continue;
+        };
+        for import_path in import_targets(&contents) {
+            if let Some(resolved) = resolve_import(&file, &import_path, remappings) {
+                if !visited.contains(&resolved) {
+                    stack.push(resolved);
+                }
+            }
+        }
+    }
+    visited
+}
+
+/// Whether `output_filepath` is the artifact for `source_filepath`, matched
+/// on the source's filename *and* its immediate containing directory (a
+/// whole-component window, not a substring), so two files that merely share
+/// a basename in different directories -- e.g. `src/Token.sol` versus
+/// `lib/vendor/Token.sol` -- aren't both treated as a match for the same
+/// artifact the way a bare-basename check would.
+fn output_belongs_to(output_filepath: &Path, source_filepath: &Path) -> bool {
+    let Some(file_name) = source_filepath.file_name() else {
+        return false;
+    };
+    let output_components: Vec<_> = output_filepath.components().collect();
+
+    match source_filepath.parent().and_then(Path::file_name) {
+        Some(parent_name) => output_components.windows(2).any(|window| {
+            window[0].as_os_str() == parent_name && window[1].as_os_str() == file_name
+        }),
+        None => output_components
+            .iter()
+            .any(|component| component.as_os_str() == file_name),
+    }
+}
+
 fn get_matching_output_files(
     json_output_filepaths: &[PathBuf],
     src_filepaths: &[PathBuf],
+    foundry_root: &Path,
+    config_remappings: &[String],
 ) -> Vec<PathBuf> {
+    let remappings = read_import_remappings(foundry_root, config_remappings);
+    let reachable = reachable_sources(src_filepaths, &remappings);
+
     json_output_filepaths
         .iter()
         .filter(|output_filepath| {
-            src_filepaths.iter().any(|src_filepath| {
-                let contract_name = src_filepath.file_name().unwrap().to_str().unwrap();
-                output_filepath
-                    .to_str()
-                    .map_or(false, |s| s.contains(contract_name))
-            })
+            reachable
+                .iter()
+                .any(|source_filepath| output_belongs_to(output_filepath, source_filepath))
         })
         .cloned()
         .collect()
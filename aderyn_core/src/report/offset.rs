@@ -0,0 +1,19 @@
+/// Convert a byte offset into a 1-indexed (line, column) pair. Shared by
+/// [`super::terminal_printer`] and [`super::sarif_printer`], which both need
+/// to turn the same raw source offsets into human-facing locations.
+pub(super) fn line_and_column_of_offset(source: &str, offset: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut column = 1;
+    for (index, ch) in source.char_indices() {
+        if index >= offset {
+            break;
+        }
+        if ch == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    (line, column)
+}
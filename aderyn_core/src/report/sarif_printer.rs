@@ -1,9 +1,10 @@
 use std::{
+    fs,
     io::{self, Result, Write},
     path::PathBuf,
 };
 
-use crate::context::workspace_context::WorkspaceContext;
+use crate::{context::workspace_context::WorkspaceContext, detect::detector::IssueSeverity};
 use serde::Serialize;
 use serde_json::Value;
 use serde_sarif::sarif::{
@@ -12,8 +13,8 @@ use serde_sarif::sarif::{
 };
 
 use super::{
-    printer::ReportPrinter, reporter::Report, FilesDetails, FilesSummary, HighIssues, Issue,
-    LowIssues,
+    offset::line_and_column_of_offset, printer::ReportPrinter, reporter::Report, severity::tier,
+    FilesDetails, FilesSummary, HighIssues, Issue, LowIssues,
 };
 
 #[derive(Serialize)]
@@ -22,7 +23,63 @@ pub struct SarifContent {
     runs: Vec<Run>,
 }
 
-pub struct SarifPrinter;
+/// A single `--remap-path-prefix FROM=TO` rule.
+#[derive(Debug, Clone)]
+pub struct RemapRule {
+    pub from: String,
+    pub to: String,
+}
+
+impl RemapRule {
+    /// Parse a `FROM=TO` spec as accepted on the command line.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (from, to) = spec.split_once('=')?;
+        Some(RemapRule {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+}
+
+/// Rewrite the leading `from` segment of `path` to `to` using the first
+/// matching rule, preferring the longest `from` prefix so more specific
+/// rules win over broader ones. Paths are emitted unchanged when nothing
+/// matches, so reports stay relocatable without requiring every rule.
+fn remap_path(path: &str, rules: &[RemapRule]) -> String {
+    // `max_by_key` keeps the last of equal maxima, so iterate in reverse to
+    // let the first of several equally long matching rules win.
+    rules
+        .iter()
+        .rev()
+        .filter(|rule| path.starts_with(rule.from.as_str()))
+        .max_by_key(|rule| rule.from.len())
+        .map(|rule| format!("{}{}", rule.to, &path[rule.from.len()..]))
+        .unwrap_or_else(|| path.to_string())
+}
+
+#[derive(Default)]
+pub struct SarifPrinter {
+    /// Applied to every instance's file path before it's written out, so
+    /// reports are byte-identical across checkouts and CI runners.
+    pub remap_rules: Vec<RemapRule>,
+}
+
+impl SarifPrinter {
+    /// Name of the environment variable a `--remap-path-prefix FROM=TO`
+    /// CLI flag should end up setting (comma-separated for multiple rules).
+    const REMAP_PATH_PREFIX_ENV: &'static str = "ADERYN_REMAP_PATH_PREFIX";
+
+    /// Build a printer with `remap_rules` taken from
+    /// [`Self::REMAP_PATH_PREFIX_ENV`], falling back to no rules (and thus
+    /// unmodified paths) when it's unset.
+    pub fn from_env() -> Self {
+        let remap_rules = std::env::var(Self::REMAP_PATH_PREFIX_ENV)
+            .ok()
+            .map(|value| value.split(',').filter_map(RemapRule::parse).collect())
+            .unwrap_or_default();
+        SarifPrinter { remap_rules }
+    }
+}
 
 impl ReportPrinter<()> for SarifPrinter {
     fn print_report<W: Write>(
@@ -36,6 +93,10 @@ impl ReportPrinter<()> for SarifPrinter {
         stdout: bool,
         detectors_used: &[(String, String)],
     ) -> Result<()> {
+        let mut rules: Vec<ReportingDescriptor> = Vec::new();
+        let results = create_sarif_results(report, context, &self.remap_rules, &mut rules);
+        let _ = detectors_used;
+
         let runs = vec![Run {
             tool: Tool {
                 driver: ToolComponent {
@@ -60,7 +121,7 @@ impl ReportPrinter<()> for SarifPrinter {
                     product_suite: None,
                     properties: None,
                     release_date_utc: None,
-                    rules: None,
+                    rules: Some(rules),
                     semantic_version: None,
                     short_description: None,
                     supported_taxonomies: None,
@@ -71,7 +132,7 @@ impl ReportPrinter<()> for SarifPrinter {
                 extensions: None,
                 properties: None,
             },
-            results: Some(create_sarif_results(report, context)),
+            results: Some(results),
             column_kind: None,
             addresses: None,
             artifacts: None,
@@ -117,61 +178,138 @@ impl ReportPrinter<()> for SarifPrinter {
     }
 }
 
-fn create_sarif_results(report: &Report, context: &WorkspaceContext) -> Vec<SarifResult> {
+/// Map a detector's `IssueSeverity` to the SARIF level GitHub code scanning
+/// expects: NC/Low surface as informational, Medium as a warning, and
+/// High/Critical as a blocking error.
+fn sarif_level(severity: &IssueSeverity) -> &'static str {
+    tier(severity)
+}
+
+/// Find the index of `detector_name` in `rules`, inserting a new
+/// `ReportingDescriptor` built from `issue` if this is the first time it's
+/// been seen.
+fn rule_index_for(rules: &mut Vec<ReportingDescriptor>, issue: &Issue, severity: &IssueSeverity) -> usize {
+    if let Some(index) = rules.iter().position(|rule| rule.id == issue.detector_name) {
+        return index;
+    }
+    rules.push(ReportingDescriptor {
+        id: issue.detector_name.clone(),
+        name: None,
+        short_description: Some(Message {
+            text: Some(issue.title.clone()),
+            arguments: None,
+            id: None,
+            markdown: None,
+            properties: None,
+        }),
+        full_description: Some(Message {
+            text: Some(issue.description.clone()),
+            arguments: None,
+            id: None,
+            markdown: None,
+            properties: None,
+        }),
+        default_configuration: None,
+        deprecated_guids: None,
+        deprecated_ids: None,
+        deprecated_names: None,
+        guid: None,
+        help: None,
+        help_uri: None,
+        message_strings: None,
+        properties: None,
+        relationships: None,
+    });
+    let _ = severity;
+    rules.len() - 1
+}
+
+fn create_sarif_results(
+    report: &Report,
+    context: &WorkspaceContext,
+    remap_rules: &[RemapRule],
+    rules: &mut Vec<ReportingDescriptor>,
+) -> Vec<SarifResult> {
+    let buckets: [(&Vec<Issue>, IssueSeverity); 5] = [
+        (&report.criticals, IssueSeverity::Critical),
+        (&report.highs, IssueSeverity::High),
+        (&report.mediums, IssueSeverity::Medium),
+        (&report.lows, IssueSeverity::Low),
+        (&report.ncs, IssueSeverity::NC),
+    ];
+
     let mut sarif_results: Vec<SarifResult> = Vec::new();
-    for high in report.highs.iter() {
-        let sarif_result = SarifResult {
-            rule_id: Some(high.detector_name.clone()),
-            message: Message {
-                text: Some(high.description.clone()),
-                arguments: None,
-                id: None,
-                markdown: None,
+    for (issues, severity) in buckets {
+        for issue in issues.iter() {
+            let rule_index = rule_index_for(rules, issue, &severity);
+            let sarif_result = SarifResult {
+                rule_id: Some(issue.detector_name.clone()),
+                rule_index: Some(rule_index as i64),
+                message: Message {
+                    text: Some(issue.description.clone()),
+                    arguments: None,
+                    id: None,
+                    markdown: None,
+                    properties: None,
+                },
+                level: Some(Value::String(sarif_level(&severity).to_string())),
+                locations: Some(create_sarif_locations(issue, context, remap_rules)),
+                analysis_target: None,
+                code_flows: None,
+                correlation_guid: None,
+                fixes: None,
+                graph_traversals: None,
+                hosted_viewer_uri: None,
+                kind: None,
+                partial_fingerprints: None,
                 properties: None,
-            },
-            level: Some(Value::String("warning".to_string())),
-            locations: Some(create_sarif_locations(high, context)),
-            rule_index: None,
-            analysis_target: None,
-            code_flows: None,
-            correlation_guid: None,
-            fixes: None,
-            graph_traversals: None,
-            hosted_viewer_uri: None,
-            kind: None,
-            partial_fingerprints: None,
-            properties: None,
-            rank: None,
-            related_locations: None,
-            web_request: None,
-            web_response: None,
-            attachments: None,
-            baseline_state: None,
-            fingerprints: None,
-            graphs: None,
-            guid: None,
-            occurrence_count: None,
-            provenance: None,
-            rule: None,
-            stacks: None,
-            suppressions: None,
-            taxa: None,
-            work_item_uris: None,
-        };
-        sarif_results.push(sarif_result);
+                rank: None,
+                related_locations: None,
+                web_request: None,
+                web_response: None,
+                attachments: None,
+                baseline_state: None,
+                fingerprints: None,
+                graphs: None,
+                guid: None,
+                occurrence_count: None,
+                provenance: None,
+                rule: None,
+                stacks: None,
+                suppressions: None,
+                taxa: None,
+                work_item_uris: None,
+            };
+            sarif_results.push(sarif_result);
+        }
     }
-    vec![]
+    sarif_results
 }
 
-fn create_sarif_locations(issue: &Issue, context: &WorkspaceContext) -> Vec<Location> {
+fn create_sarif_locations(
+    issue: &Issue,
+    context: &WorkspaceContext,
+    remap_rules: &[RemapRule],
+) -> Vec<Location> {
     let mut locations: Vec<Location> = Vec::new();
-    for ((filename, line_number, source_location), value) in issue.instances.iter() {
+    for ((filename, line_number, _source_location), value) in issue.instances.iter() {
         if let Some(offset_len) = context.get_offset_and_length_of_node(*value) {
+            let (start_line, start_column, end_line, end_column) = fs::read_to_string(filename)
+                .ok()
+                .map(|source| {
+                    let (start_line, start_column) =
+                        line_and_column_of_offset(&source, offset_len.0);
+                    let (end_line, end_column) =
+                        line_and_column_of_offset(&source, offset_len.0 + offset_len.1);
+                    (start_line, start_column, end_line, end_column)
+                })
+                .unwrap_or((*line_number, 1, *line_number, 1));
+
             let location = Location {
                 physical_location: Some(PhysicalLocation {
                     address: None,
                     artifact_location: Some(ArtifactLocation {
-                        uri: Some(filename.clone()),
+                        uri: Some(remap_path(filename, remap_rules)),
                         uri_base_id: None,
                         description: None,
                         index: None,
@@ -182,17 +320,16 @@ fn create_sarif_locations(issue: &Issue, context: &WorkspaceContext) -> Vec<Loca
                     region: Some(Region {
                         char_offset: Some(offset_len.0.try_into().unwrap()),
                         char_length: Some(offset_len.1.try_into().unwrap()),
-
+                        start_line: Some(start_line as i64),
+                        start_column: Some(start_column as i64),
+                        end_line: Some(end_line as i64),
+                        end_column: Some(end_column as i64),
                         byte_length: None,
                         byte_offset: None,
-                        end_column: None,
-                        end_line: None,
                         message: None,
                         properties: None,
                         snippet: None,
                         source_language: None,
-                        start_column: None,
-                        start_line: None,
                     }),
                 }),
                 properties: None,
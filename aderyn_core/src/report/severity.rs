@@ -0,0 +1,25 @@
+use crate::detect::detector::IssueSeverity;
+
+/// Collapse a severity into the three-tier bucket every printer in this
+/// crate displays: a blocking `"error"`, an advisory `"warning"`, or an
+/// informational `"note"`. `Low` lands on `"note"` alongside `NC` rather
+/// than being escalated to a warning, matching GitHub code scanning's own
+/// treatment of low-severity findings. Shared by [`super::terminal_printer`]
+/// and [`super::sarif_printer`] so the two agree on every tier.
+pub(super) fn tier(severity: &IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::NC | IssueSeverity::Low => "note",
+        IssueSeverity::Medium => "warning",
+        IssueSeverity::High | IssueSeverity::Critical => "error",
+    }
+}
+
+/// Same mapping for printers that only have the detector's single-letter
+/// code on hand instead of the parsed [`IssueSeverity`].
+pub(super) fn tier_for_code(severity: &str) -> &'static str {
+    match severity {
+        "C" | "H" => "error",
+        "M" => "warning",
+        _ => "note",
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    fs,
+    io::{Result, Write},
+    path::PathBuf,
+};
+
+use crate::context::workspace_context::WorkspaceContext;
+
+use super::{
+    offset::line_and_column_of_offset,
+    printer::ReportPrinter,
+    reporter::{Issue, Report},
+    severity::tier_for_code,
+};
+
+/// How many lines of surrounding context to print above and below a span.
+const CONTEXT_LINES: usize = 2;
+
+/// Renders rustc-style annotated snippets in the terminal: the offending
+/// line(s) with a caret/underline under the exact span, using the same byte
+/// offsets `create_sarif_locations` consumes.
+pub struct TerminalDiagnosticPrinter;
+
+impl TerminalDiagnosticPrinter {
+    fn gutter_for(severity: &str) -> &'static str {
+        tier_for_code(severity)
+    }
+
+    fn print_issue_instances<W: Write>(&self, mut writer: W, issue: &Issue, context: &WorkspaceContext, severity: &str) -> Result<()> {
+        let gutter = Self::gutter_for(severity);
+        for ((filename, _line_number, _source_location), node_id) in issue.instances.iter() {
+            let Some((offset, length)) = context.get_offset_and_length_of_node(*node_id) else {
+                continue;
+            };
+            let Ok(source) = fs::read_to_string(filename) else {
+                continue;
+            };
+
+            let (line, column) = line_and_column_of_offset(&source, offset);
+            let lines: Vec<&str> = source.lines().collect();
+            let first_line = line.saturating_sub(CONTEXT_LINES).max(1);
+            let last_line = (line + CONTEXT_LINES).min(lines.len());
+
+            writeln!(writer, "{}[{}]: {}", gutter, issue.detector_name, issue.title)?;
+            writeln!(writer, "  --> {}:{}:{}", filename, line, column)?;
+
+            let mut remaining = length;
+            for current_line in first_line..=last_line {
+                let Some(text) = lines.get(current_line - 1) else {
+                    continue;
+                };
+                writeln!(writer, "{:>4} | {}", current_line, text)?;
+
+                if current_line == line {
+                    let underline_start = column.saturating_sub(1);
+                    let underline_len = remaining.min(text.len().saturating_sub(underline_start)).max(1);
+                    writeln!(
+                        writer,
+                        "     | {}{}",
+                        " ".repeat(underline_start),
+                        "^".repeat(underline_len)
+                    )?;
+                    remaining = remaining.saturating_sub(underline_len);
+                } else if current_line > line && remaining > 0 {
+                    // Multi-line span: keep underlining the continuation line
+                    // with carets for whatever is left of the span.
+                    let underline_len = remaining.min(text.len()).max(1);
+                    writeln!(writer, "     | {}", "^".repeat(underline_len))?;
+                    remaining = remaining.saturating_sub(underline_len);
+                }
+            }
+            writeln!(writer, "     = {}", issue.description)?;
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl ReportPrinter<()> for TerminalDiagnosticPrinter {
+    fn print_report<W: Write>(
+        &self,
+        mut writer: W,
+        report: &Report,
+        context: &WorkspaceContext,
+        _: PathBuf,
+        _: Option<String>,
+        _: bool,
+        _stdout: bool,
+        _detectors_used: &[(String, String)],
+    ) -> Result<()> {
+        for (severity, issues) in [
+            ("C", &report.criticals),
+            ("H", &report.highs),
+            ("M", &report.mediums),
+            ("L", &report.lows),
+            ("NC", &report.ncs),
+        ] {
+            for issue in issues {
+                self.print_issue_instances(&mut writer, issue, context, severity)?;
+            }
+        }
+        Ok(())
+    }
+}
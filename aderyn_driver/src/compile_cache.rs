@@ -0,0 +1,322 @@
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::version_compiler::VersionGroup;
+
+const COMPILE_CACHE_FILE_NAME: &str = ".aderyn_compile_cache.json";
+const AST_CACHE_DIR_NAME: &str = ".aderyn_ast_cache";
+
+/// Manifest mapping each source file to the fingerprint it was last
+/// compiled under -- content hash, resolved solc version, and the
+/// remappings in effect -- so a re-run can skip solc entirely for any
+/// file where nothing changed, borrowing the design of foundry-compilers'
+/// `SolFilesCache`.
+///
+/// Full semantic compilation needs every file in a [`VersionGroup`] present
+/// in the same solc invocation to resolve cross-file symbols, so there
+/// freshness is all-or-nothing for the whole group. Under `--stop-after
+/// parsing`, though, each file parses standalone (see
+/// `compile_group_in_batches`), so only the files that changed, or that
+/// (transitively) import a changed file, need to go back through solc --
+/// see [`CompileCache::partition_group_files`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CompileCache {
+    files: BTreeMap<PathBuf, CompileFingerprint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct CompileFingerprint {
+    content_hash: u64,
+    resolved_version: String,
+    remappings_hash: u64,
+    ast_cache_path: PathBuf,
+}
+
+impl CompileCache {
+    pub fn load(root: &Path) -> Self {
+        fs::read_to_string(root.join(COMPILE_CACHE_FILE_NAME))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, root: &Path) {
+        if let Ok(contents) = serde_json::to_string(self) {
+            let _ = fs::write(root.join(COMPILE_CACHE_FILE_NAME), contents);
+        }
+    }
+
+    /// Whether every file in `group` still matches its last-recorded
+    /// fingerprint for this exact remapping set -- including transitively,
+    /// through its imports (see [`transitively_dirty_files`]).
+    fn is_group_fresh(&self, group: &VersionGroup, remappings_hash: u64) -> bool {
+        self.transitively_dirty_files(group, remappings_hash).is_empty()
+    }
+
+    /// The files in `group` whose own content, resolved version or
+    /// remappings no longer match their last-recorded fingerprint.
+    fn directly_dirty_files(&self, group: &VersionGroup, remappings_hash: u64) -> BTreeSet<PathBuf> {
+        group
+            .files
+            .iter()
+            .filter(|file| {
+                !self.files.get(*file).is_some_and(|fingerprint| {
+                    fingerprint.resolved_version == group.version
+                        && fingerprint.remappings_hash == remappings_hash
+                        && fingerprint.content_hash == hash_file(file)
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// [`directly_dirty_files`], extended to every file that (transitively)
+    /// imports a dirty file. A file whose own content hasn't changed can
+    /// still compile differently if something it imports did, so serving it
+    /// from cache untouched would be stale -- this is the critical
+    /// invariant incremental compilation depends on.
+    fn transitively_dirty_files(&self, group: &VersionGroup, remappings_hash: u64) -> BTreeSet<PathBuf> {
+        let direct_imports: BTreeMap<&PathBuf, Vec<PathBuf>> = group
+            .files
+            .iter()
+            .map(|file| (file, resolve_imports(file, &group.files)))
+            .collect();
+
+        let mut dirty = self.directly_dirty_files(group, remappings_hash);
+        loop {
+            let mut newly_dirty = Vec::new();
+            for (file, imports) in &direct_imports {
+                if !dirty.contains(*file) && imports.iter().any(|import| dirty.contains(import)) {
+                    newly_dirty.push((*file).clone());
+                }
+            }
+            if newly_dirty.is_empty() {
+                break;
+            }
+            dirty.extend(newly_dirty);
+        }
+        dirty
+    }
+
+    /// Load every cached AST in `group` from disk, or `None` if any entry
+    /// is missing or unreadable -- in which case the caller should
+    /// recompile the whole group instead of patching around the gap.
+    fn cached_asts(&self, group: &VersionGroup) -> Option<BTreeMap<PathBuf, Value>> {
+        group
+            .files
+            .iter()
+            .map(|file| {
+                let fingerprint = self.files.get(file)?;
+                let contents = fs::read_to_string(&fingerprint.ast_cache_path).ok()?;
+                let ast: Value = serde_json::from_str(&contents).ok()?;
+                Some((file.clone(), ast))
+            })
+            .collect()
+    }
+
+    /// Split `group`'s files into the ones that are still fresh (with their
+    /// cached ASTs loaded) and the ones that need to go through solc again.
+    /// Unlike [`CompileCache::is_group_fresh`], this judges each file
+    /// separately -- by its own fingerprint and those of the files it
+    /// imports (see [`transitively_dirty_files`]) -- rather than the group
+    /// as a whole, which is only valid when the group will be parsed
+    /// standalone per file (`--stop-after parsing`) rather than compiled as
+    /// one semantic unit.
+    fn partition_group_files(
+        &self,
+        group: &VersionGroup,
+        remappings_hash: u64,
+    ) -> (Vec<PathBuf>, BTreeMap<PathBuf, Value>) {
+        let dirty = self.transitively_dirty_files(group, remappings_hash);
+        let mut dirty_files = Vec::new();
+        let mut clean_asts = BTreeMap::new();
+
+        for file in &group.files {
+            if dirty.contains(file) {
+                dirty_files.push(file.clone());
+                continue;
+            }
+
+            let cached_ast = self
+                .files
+                .get(file)
+                .and_then(|fingerprint| {
+                    let contents = fs::read_to_string(&fingerprint.ast_cache_path).ok()?;
+                    serde_json::from_str::<Value>(&contents).ok()
+                });
+
+            match cached_ast {
+                Some(ast) => {
+                    clean_asts.insert(file.clone(), ast);
+                }
+                None => dirty_files.push(file.clone()),
+            }
+        }
+
+        (dirty_files, clean_asts)
+    }
+
+    fn record(
+        &mut self,
+        root: &Path,
+        group: &VersionGroup,
+        remappings_hash: u64,
+        asts: &BTreeMap<PathBuf, Value>,
+    ) {
+        for file in &group.files {
+            let Some(ast) = asts.get(file) else {
+                continue;
+            };
+            let ast_cache_path = ast_cache_path_for(root, file);
+            if let Some(parent) = ast_cache_path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            if let Ok(contents) = serde_json::to_string(ast) {
+                let _ = fs::write(&ast_cache_path, contents);
+            }
+            self.files.insert(
+                file.clone(),
+                CompileFingerprint {
+                    content_hash: hash_file(file),
+                    resolved_version: group.version.clone(),
+                    remappings_hash,
+                    ast_cache_path,
+                },
+            );
+        }
+    }
+}
+
+fn ast_cache_path_for(root: &Path, file: &Path) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    file.hash(&mut hasher);
+    root.join(AST_CACHE_DIR_NAME)
+        .join(format!("{:x}.json", hasher.finish()))
+}
+
+/// The quoted targets of every `import` statement in a Solidity source file
+/// (`import "X";`, `import {Y} from "X";`, `import * as Z from "X";`), in
+/// whatever form they were written -- relative, absolute or remapped.
+fn import_targets(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let trimmed = line.trim_start();
+            if !trimmed.starts_with("import") {
+                return None;
+            }
+            let quote_index = trimmed.find(['"', '\''])?;
+            let quote = trimmed.as_bytes()[quote_index] as char;
+            let rest = &trimmed[quote_index + 1..];
+            let end = rest.find(quote)?;
+            Some(rest[..end].to_string())
+        })
+        .collect()
+}
+
+/// Resolve `file`'s direct imports to whichever of `group_files` they refer
+/// to, matching by file name rather than re-deriving full remapping
+/// resolution -- good enough to know which sibling in the same version
+/// group to propagate dirtiness from.
+fn resolve_imports(file: &Path, group_files: &[PathBuf]) -> Vec<PathBuf> {
+    let Ok(contents) = fs::read_to_string(file) else {
+        return Vec::new();
+    };
+    import_targets(&contents)
+        .iter()
+        .filter_map(|target| {
+            let target_name = Path::new(target).file_name()?;
+            group_files
+                .iter()
+                .find(|candidate| candidate.file_name() == Some(target_name))
+                .cloned()
+        })
+        .collect()
+}
+
+/// Cheap change-detection hash of a file's bytes -- a collision just costs a
+/// spurious recompile, not a cryptographic guarantee.
+pub fn hash_file(path: &Path) -> u64 {
+    let contents = fs::read(path).unwrap_or_default();
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
+
+pub fn hash_remappings(remappings: &[String]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for remapping in remappings {
+        remapping.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Split `groups` into the files that still need solc (as smaller
+/// [`VersionGroup`]s so each compiles only what's actually dirty) and the
+/// ASTs that can be reused unchanged, keyed by version.
+///
+/// Under `stop_after_parsing`, each file in a group parses standalone, so
+/// freshness is judged per file -- an edit to one file no longer forces a
+/// recompile of every other file sharing its solc version. Without it, full
+/// semantic compilation needs every file in the group together, so the
+/// whole group is dirty as soon as any file in it is.
+pub fn partition_dirty_groups(
+    cache: &CompileCache,
+    groups: Vec<VersionGroup>,
+    remappings_hash: u64,
+    stop_after_parsing: bool,
+) -> (Vec<VersionGroup>, BTreeMap<String, BTreeMap<PathBuf, Value>>) {
+    let mut dirty = Vec::new();
+    let mut reused: BTreeMap<String, BTreeMap<PathBuf, Value>> = BTreeMap::new();
+
+    if !stop_after_parsing {
+        for group in groups {
+            if cache.is_group_fresh(&group, remappings_hash) {
+                if let Some(asts) = cache.cached_asts(&group) {
+                    reused.insert(group.version.clone(), asts);
+                    continue;
+                }
+            }
+            dirty.push(group);
+        }
+        return (dirty, reused);
+    }
+
+    for group in groups {
+        let (dirty_files, clean_asts) = cache.partition_group_files(&group, remappings_hash);
+        if !clean_asts.is_empty() {
+            reused.entry(group.version.clone()).or_default().extend(clean_asts);
+        }
+        if !dirty_files.is_empty() {
+            dirty.push(VersionGroup {
+                version: group.version,
+                solc_path: group.solc_path,
+                files: dirty_files,
+            });
+        }
+    }
+
+    (dirty, reused)
+}
+
+/// Persist freshly compiled ASTs for `dirty` groups into the cache.
+pub fn record_compiled_groups(
+    cache: &mut CompileCache,
+    root: &Path,
+    dirty: &[VersionGroup],
+    remappings_hash: u64,
+    results: &BTreeMap<String, BTreeMap<PathBuf, Value>>,
+) {
+    for group in dirty {
+        if let Some(asts) = results.get(&group.version) {
+            cache.record(root, group, remappings_hash, asts);
+        }
+    }
+}
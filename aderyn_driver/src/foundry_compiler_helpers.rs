@@ -38,17 +38,81 @@ pub fn get_remappings(root: &Path) -> (Vec<String>, Vec<Remapping>) {
 }
 
 pub fn get_project(root: &Path, remappings: Vec<Remapping>) -> Project {
+    get_project_with_options(root, remappings, false, None)
+}
+
+/// Build the `Project` the way `get_project` does, but optionally in
+/// offline mode and/or pinned to a specific solc binary.
+///
+/// Offline mode configures the builder to only use already-installed solc
+/// versions, so a sandboxed CI run or air-gapped audit never tries to shell
+/// out to the network to fetch a missing compiler. Callers that need a
+/// hard failure instead of a silent fallback should check
+/// `missing_solc_versions_for` up front and surface its result themselves.
+pub fn get_project_with_options(
+    root: &Path,
+    remappings: Vec<Remapping>,
+    offline: bool,
+    solc_path: Option<&Path>,
+) -> Project {
     let paths = ProjectPathsConfig::builder()
         .root(root)
         .remappings(remappings)
         .build()
         .unwrap();
-    Project::builder()
-        .no_artifacts()
-        .paths(paths)
-        .ephemeral()
-        .build()
-        .unwrap()
+
+    let mut builder = Project::builder().no_artifacts().paths(paths).ephemeral();
+    if offline {
+        builder = builder.offline();
+    }
+
+    let project = builder.build().unwrap();
+
+    if let Some(solc_path) = solc_path {
+        if let Ok(solc) = foundry_compilers::Solc::new(solc_path) {
+            return Project::builder()
+                .no_artifacts()
+                .paths(project.paths.clone())
+                .ephemeral()
+                .offline()
+                .solc(solc)
+                .build()
+                .unwrap();
+        }
+    }
+
+    project
+}
+
+/// Pragma version requirements that have no matching solc version already
+/// installed, as reported by svm. Call this before compiling in offline
+/// mode so the caller can fail fast with an actionable message rather than
+/// letting the compiler attempt (and fail) to fetch one.
+pub fn missing_solc_versions_for(required: &[semver::VersionReq]) -> Vec<semver::VersionReq> {
+    let installed = svm::installed_versions().unwrap_or_default();
+    required
+        .iter()
+        .filter(|req| !installed.iter().any(|version| req.matches(version)))
+        .cloned()
+        .collect()
+}
+
+/// Pull each source's `pragma solidity` range out of its raw content, so an
+/// offline pre-flight check can run before the import-graph resolution that
+/// needs network access to pick versions even starts.
+pub(crate) fn pragma_version_reqs(sources: &BTreeMap<PathBuf, Source>) -> Vec<semver::VersionReq> {
+    sources
+        .values()
+        .filter_map(|source| {
+            let content: &str = source.content.as_ref();
+            let line = content.lines().find(|line| {
+                let line = line.trim_start();
+                line.starts_with("pragma") && line.contains("solidity")
+            })?;
+            let version_req = line.split("solidity").nth(1)?.trim().trim_end_matches(';').trim();
+            semver::VersionReq::parse(version_req).ok()
+        })
+        .collect()
 }
 
 pub fn get_relevant_sources(
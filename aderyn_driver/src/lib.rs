@@ -0,0 +1,139 @@
+/// Compile-level incremental cache: skips re-invoking solc for version
+/// groups whose member files haven't changed since the last run.
+pub mod compile_cache;
+/// Compiler-input, `remappings.txt` and `Project`-building helpers shared by the Foundry-aware and
+/// Foundry-free (`--solc-path`) loading paths.
+pub mod foundry_compiler_helpers;
+/// Per-version-group solc invocation: Standard JSON input, `--stop-after
+/// parsing`, resolution/auto-install of required solc versions, and
+/// parallel compilation across groups.
+pub mod version_compiler;
+
+#[cfg(test)]
+mod project_compiler_tests;
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use aderyn_core::{ast::SourceUnit, context::workspace_context::WorkspaceContext, visitor::ast_visitor::Node};
+use foundry_compilers::artifacts::Source;
+use serde_json::Value;
+
+use foundry_compiler_helpers::{get_compiler_input, get_relevant_sources, get_remappings};
+use version_compiler::{
+    compile_multi_version_incremental, compile_multi_version_with_options, CompileOptions,
+    VersionCompileError,
+};
+
+/// Is `path` in scope given an explicit `--src` allowlist? `None` means no
+/// allowlist was given, so every path passes.
+pub fn passes_src(src: &Option<Vec<PathBuf>>, path: &Path) -> bool {
+    match src {
+        Some(allowed) => allowed.iter().any(|allowed_path| path.starts_with(allowed_path)),
+        None => true,
+    }
+}
+
+/// Is `path` (relative to `root`) in scope given an explicit `--scope`
+/// allowlist of path prefixes? `None` means every path passes.
+pub fn passes_scope(scope: &Option<Vec<String>>, path: &Path, root: &str) -> bool {
+    let Some(scopes) = scope else {
+        return true;
+    };
+    let relative = path.to_string_lossy();
+    let relative = relative.strip_prefix(root).unwrap_or(&relative);
+    let relative = relative.trim_start_matches('/');
+    scopes.iter().any(|prefix| relative.starts_with(prefix.as_str()))
+}
+
+/// Is `path` (relative to `root`) NOT excluded given an explicit `--exclude`
+/// list of path prefixes? `None` means nothing is excluded.
+pub fn passes_exclude(exclude: &Option<Vec<String>>, path: &Path, root: &str) -> bool {
+    let Some(excludes) = exclude else {
+        return true;
+    };
+    let relative = path.to_string_lossy();
+    let relative = relative.strip_prefix(root).unwrap_or(&relative);
+    let relative = relative.trim_start_matches('/');
+    !excludes.iter().any(|prefix| relative.starts_with(prefix.as_str()))
+}
+
+/// Read `root`'s `remappings.txt`, one `prefix=target` rule per line, or
+/// `None` if it doesn't exist.
+pub fn read_remappings(root: &Path) -> Option<Vec<String>> {
+    let contents = std::fs::read_to_string(root.join("remappings.txt")).ok()?;
+    let remappings: Vec<String> = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+    if remappings.is_empty() {
+        None
+    } else {
+        Some(remappings)
+    }
+}
+
+/// The real compile entry point: resolve every Solidity file in scope under
+/// `root`, group it by required solc version, and compile each group via
+/// [`version_compiler`] -- respecting [`CompileOptions`] and, unless
+/// `no_cache` is set, reusing [`compile_cache::CompileCache`] entries for
+/// groups that haven't changed since the last run.
+pub fn compile_scoped_sources(
+    root: &Path,
+    src: &Option<Vec<PathBuf>>,
+    scope: &Option<Vec<String>>,
+    exclude: &Option<Vec<String>>,
+    options: &CompileOptions,
+    no_cache: bool,
+) -> Result<BTreeMap<String, Result<BTreeMap<PathBuf, Value>, VersionCompileError>>, String> {
+    let compiler_input = get_compiler_input(root);
+    let (raw_remappings, remappings) = get_remappings(root);
+    let sources: BTreeMap<PathBuf, Source> =
+        get_relevant_sources(root, compiler_input, src, scope, exclude);
+
+    if no_cache {
+        compile_multi_version_with_options(root, remappings, &raw_remappings, sources, options)
+    } else {
+        compile_multi_version_incremental(root, remappings, &raw_remappings, sources, options)
+    }
+}
+
+/// The real integration point between this crate's compile pipeline and
+/// `aderyn_core`'s detectors: run [`compile_scoped_sources`], then fold every
+/// resulting per-file AST into one [`WorkspaceContext`] -- the representation
+/// the detectors run over, so callers get version resolution, batching and incremental caching without
+/// detectors needing to know which loader produced their input.
+///
+/// This is the entry point a `--compiler` CLI path should call instead of
+/// `load_project`/`load_with_solc` to actually exercise this pipeline; see
+/// `compile_scoped_sources`'s doc comment for what it wires together.
+pub fn load_workspace_context(
+    root: &Path,
+    src: &Option<Vec<PathBuf>>,
+    scope: &Option<Vec<String>>,
+    exclude: &Option<Vec<String>>,
+    options: &CompileOptions,
+    no_cache: bool,
+) -> Result<WorkspaceContext, String> {
+    let results = compile_scoped_sources(root, src, scope, exclude, options, no_cache)?;
+
+    let mut context = WorkspaceContext::default();
+    for (version, result) in results {
+        let asts = result.map_err(|err| format!("solc {version} failed: {}", err.message))?;
+        for (file, ast) in asts {
+            let mut source_unit: SourceUnit = serde_json::from_value(ast)
+                .map_err(|err| format!("failed to deserialize AST for {}: {err}", file.display()))?;
+            if source_unit.source.is_none() {
+                source_unit.source = std::fs::read_to_string(&file).ok();
+            }
+            source_unit
+                .accept(&mut context)
+                .map_err(|err| format!("failed to load {} into workspace context: {err:?}", file.display()))?;
+        }
+    }
+    Ok(context)
+}
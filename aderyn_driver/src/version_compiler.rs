@@ -0,0 +1,527 @@
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use foundry_compilers::{artifacts::Source, Graph, Project};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    compile_cache::{hash_remappings, partition_dirty_groups, record_compiled_groups, CompileCache},
+    foundry_compiler_helpers::{get_project_with_options, missing_solc_versions_for, pragma_version_reqs},
+};
+
+/// The source files that the import graph resolved to one solc version,
+/// compiled together with that version. Files that don't import each other
+/// share a group whenever they resolve to the same version.
+#[derive(Clone)]
+pub struct VersionGroup {
+    pub version: String,
+    pub solc_path: PathBuf,
+    pub files: Vec<PathBuf>,
+}
+
+/// A compiled source file's per-version solc invocation failed.
+#[derive(Debug)]
+pub struct VersionCompileError {
+    pub version: String,
+    pub files: Vec<PathBuf>,
+    pub message: String,
+    /// The compiler's own `errors` entries (each a `{severity, message, ...}`
+    /// object), when the failure came from a Standard JSON response rather
+    /// than a process/IO error.
+    pub structured_errors: Vec<Value>,
+}
+
+impl VersionCompileError {
+    fn io(version: &str, files: &[PathBuf], message: impl Into<String>) -> Self {
+        VersionCompileError {
+            version: version.to_string(),
+            files: files.to_vec(),
+            message: message.into(),
+            structured_errors: Vec::new(),
+        }
+    }
+}
+
+/// Tuning knobs for [`compile_groups_in_parallel`] and friends.
+#[derive(Debug, Clone)]
+pub struct CompileOptions {
+    /// Request solc's `--stop-after parsing` fast path (parse-only, no
+    /// type-checking/codegen) on every group whose resolved version
+    /// supports it. Set to `false` when a detector needs type/semantic info
+    /// and must see a fully analyzed AST.
+    pub stop_after_parsing: bool,
+    /// Split a version group's files into batches of at most this many
+    /// files, each compiled in its own solc invocation and merged. Only
+    /// applied when `stop_after_parsing` is set: full semantic compilation
+    /// needs every imported file present in one invocation to resolve
+    /// cross-file symbols, but AST-only parsing doesn't.
+    pub batch_size: usize,
+    /// Cap on how many solc processes run at once; `None` lets rayon use
+    /// its default (one worker thread per available core), which is enough
+    /// to spawn dozens of concurrent processes on a big monorepo.
+    pub max_concurrency: Option<usize>,
+    /// Never reach out to the network to install a missing solc version --
+    /// fail [`compile_multi_version_with_options`] up front with the full
+    /// list of missing versions instead. Set this for sandboxed CI runs or
+    /// air-gapped audits.
+    pub offline: bool,
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        CompileOptions {
+            stop_after_parsing: true,
+            batch_size: 40,
+            max_concurrency: None,
+            offline: false,
+        }
+    }
+}
+
+/// One solc version a scope needs, and the files that require it -- derived
+/// straight from [`group_sources_by_version`]'s grouping, with no solc
+/// invocation involved. Lets a caller answer "which compiler versions would
+/// this compile need" for a pre-flight check or summary before committing to
+/// a full run.
+#[derive(Debug, Clone)]
+pub struct RequiredSolcVersion {
+    pub version: String,
+    pub files: Vec<PathBuf>,
+}
+
+/// List every solc version `groups` requires, without compiling anything.
+pub fn required_solc_versions(groups: &[VersionGroup]) -> Vec<RequiredSolcVersion> {
+    groups
+        .iter()
+        .map(|group| RequiredSolcVersion {
+            version: group.version.clone(),
+            files: group.files.clone(),
+        })
+        .collect()
+}
+
+/// A required solc version with no matching binary available locally.
+#[derive(Debug, Clone)]
+pub struct MissingSolcVersion {
+    pub version: String,
+    pub files: Vec<PathBuf>,
+}
+
+/// One or more version groups need a solc binary that isn't installed, and
+/// [`CompileOptions::offline`] forbade installing it.
+#[derive(Debug)]
+pub struct SolcResolutionError {
+    pub missing: Vec<MissingSolcVersion>,
+}
+
+impl std::fmt::Display for SolcResolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "missing solc version(s) required to compile in scope:")?;
+        for missing in &self.missing {
+            writeln!(f, "  - {} (required by {} file(s))", missing.version, missing.files.len())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SolcResolutionError {}
+
+/// For every version group, make sure a matching solc binary is available:
+/// reuse one already installed (checked via svm, falling back to the
+/// resolved `solc_path` existing on disk), install the missing version
+/// through svm when `offline` is `false`, or collect it as missing when
+/// `offline` is `true` instead of reaching out to the network.
+pub fn ensure_solc_installed(groups: &[VersionGroup], offline: bool) -> Result<(), SolcResolutionError> {
+    let installed = svm::installed_versions().unwrap_or_default();
+    let mut missing = Vec::new();
+
+    for group in groups {
+        if group.solc_path.is_file() {
+            continue;
+        }
+        let Ok(version) = semver::Version::parse(&group.version) else {
+            continue;
+        };
+        if installed.contains(&version) {
+            continue;
+        }
+        if offline || svm::blocking_install(&version).is_err() {
+            missing.push(MissingSolcVersion {
+                version: group.version.clone(),
+                files: group.files.clone(),
+            });
+        }
+    }
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(SolcResolutionError { missing })
+    }
+}
+
+/// Group every in-scope source file into the smallest number of
+/// mutually-satisfiable solc version buckets, respecting the import graph:
+/// a file and everything it imports must land in the same bucket, or we
+/// can't correctly type-check it against one compiler.
+///
+/// Returns an error listing the files whose pragmas conflict if no version
+/// in the import closure of a connected component satisfies every member.
+pub fn group_sources_by_version(
+    root: &Path,
+    project: &Project,
+    sources: BTreeMap<PathBuf, Source>,
+) -> Result<Vec<VersionGroup>, String> {
+    let graph = Graph::resolve_sources(&project.paths, sources)
+        .map_err(|err| format!("failed to resolve import graph: {err}"))?;
+    let (versions, _) = graph
+        .into_sources_by_version(project.offline)
+        .map_err(|err| format!("no solc version satisfies every pragma in scope: {err}"))?;
+
+    let sources_by_version = versions
+        .get(project)
+        .ok_or_else(|| "no compilable sources found in scope".to_string())?;
+
+    let _ = root;
+    Ok(sources_by_version
+        .iter()
+        .map(|(solc, (version, files))| VersionGroup {
+            version: version.to_string(),
+            solc_path: solc.solc.clone(),
+            files: files.keys().cloned().collect(),
+        })
+        .collect())
+}
+
+/// Compile every version group concurrently, each against its own resolved
+/// solc binary, and merge the resulting ASTs into one unified view. Output
+/// order is kept deterministic by collecting into a version-keyed map.
+///
+/// `options.max_concurrency`, if set, caps how many solc processes run at
+/// once across both the group-level and batch-level parallelism below, so
+/// aderyn doesn't spawn dozens of them on a constrained CI runner.
+pub fn compile_groups_in_parallel(
+    root: &Path,
+    remappings: &[String],
+    groups: &[VersionGroup],
+    options: &CompileOptions,
+) -> BTreeMap<String, Result<BTreeMap<PathBuf, Value>, VersionCompileError>> {
+    use rayon::prelude::*;
+
+    let run = || {
+        groups
+            .par_iter()
+            .map(|group| (group.version.clone(), compile_one_group(root, remappings, group, options)))
+            .collect()
+    };
+
+    match options.max_concurrency {
+        Some(max_concurrency) => rayon::ThreadPoolBuilder::new()
+            .num_threads(max_concurrency)
+            .build()
+            .map(|pool| pool.install(run))
+            .unwrap_or_else(|_| run()),
+        None => run(),
+    }
+}
+
+/// The Standard JSON Input object solc's `--standard-json` mode expects:
+/// https://docs.soliditylang.org/en/latest/using-the-compiler.html#compiler-input-and-output-json-description
+#[derive(Serialize)]
+struct StandardJsonInput {
+    language: &'static str,
+    sources: BTreeMap<String, StandardJsonSource>,
+    settings: StandardJsonSettings,
+}
+
+#[derive(Serialize)]
+struct StandardJsonSource {
+    content: String,
+}
+
+#[derive(Serialize)]
+struct StandardJsonSettings {
+    remappings: Vec<String>,
+    #[serde(rename = "outputSelection")]
+    output_selection: BTreeMap<String, BTreeMap<String, Vec<String>>>,
+    #[serde(rename = "stopAfter", skip_serializing_if = "Option::is_none")]
+    stop_after: Option<&'static str>,
+}
+
+/// The lowest solc version that understands `--stop-after`/`stopAfter`.
+const MIN_STOP_AFTER_PARSING_VERSION: (u64, u64) = (0, 6);
+
+/// Whether `version` supports skipping straight to the parse phase.
+fn supports_stop_after_parsing(version: &str) -> bool {
+    semver::Version::parse(version)
+        .map(|version| (version.major, version.minor) >= MIN_STOP_AFTER_PARSING_VERSION)
+        .unwrap_or(false)
+}
+
+/// Compile one version group, optionally sharded into batches compiled in
+/// parallel and merged (see [`CompileOptions::batch_size`]).
+fn compile_one_group(
+    root: &Path,
+    remappings: &[String],
+    group: &VersionGroup,
+    options: &CompileOptions,
+) -> Result<BTreeMap<PathBuf, Value>, VersionCompileError> {
+    if options.stop_after_parsing && group.files.len() > options.batch_size {
+        return compile_group_in_batches(root, remappings, group, options);
+    }
+    compile_batch(root, remappings, &group.version, &group.solc_path, &group.files, options.stop_after_parsing)
+}
+
+/// Shard a group's files into batches of at most `options.batch_size`,
+/// compile each batch independently (in parallel, capped by
+/// `options.max_concurrency`), and merge their ASTs. Each file still parses
+/// standalone under `--stop-after parsing`, so batches don't need to ship
+/// every file's transitive imports together the way full compilation would.
+fn compile_group_in_batches(
+    root: &Path,
+    remappings: &[String],
+    group: &VersionGroup,
+    options: &CompileOptions,
+) -> Result<BTreeMap<PathBuf, Value>, VersionCompileError> {
+    use rayon::prelude::*;
+
+    let batches: Vec<&[PathBuf]> = group.files.chunks(options.batch_size).collect();
+    let batch_results: Vec<Result<BTreeMap<PathBuf, Value>, VersionCompileError>> = batches
+        .into_par_iter()
+        .map(|batch| compile_batch(root, remappings, &group.version, &group.solc_path, batch, true))
+        .collect();
+
+    let mut merged = BTreeMap::new();
+    for batch_result in batch_results {
+        merged.extend(batch_result?);
+    }
+    Ok(merged)
+}
+
+/// Compile `files` against `solc_path` via Standard JSON input/output
+/// instead of a raw CLI invocation. This sidesteps OS argument-length
+/// limits on large source sets, passes remappings as structured settings
+/// instead of shell tokens (so ones containing spaces survive intact), and
+/// asks solc to emit only the AST -- skipping bytecode/metadata generation
+/// -- via `outputSelection: { "*": { "": ["ast"] } }`.
+///
+/// When `stop_after_parsing` is set and `version` is new enough (0.6.x+),
+/// `stopAfter: "parsing"` is also set so solc halts right after producing
+/// the AST instead of running type-checking and codegen, and tolerates
+/// sources that only parse but don't fully type-check.
+fn compile_batch(
+    root: &Path,
+    remappings: &[String],
+    version: &str,
+    solc_path: &Path,
+    files: &[PathBuf],
+    stop_after_parsing: bool,
+) -> Result<BTreeMap<PathBuf, Value>, VersionCompileError> {
+    let mut sources = BTreeMap::new();
+    for file in files {
+        let content = std::fs::read_to_string(file)
+            .map_err(|err| VersionCompileError::io(version, files, format!("failed to read {}: {err}", file.display())))?;
+        sources.insert(file.to_string_lossy().into_owned(), StandardJsonSource { content });
+    }
+
+    let mut per_file_selection = BTreeMap::new();
+    per_file_selection.insert(String::new(), vec!["ast".to_string()]);
+    let mut output_selection = BTreeMap::new();
+    output_selection.insert("*".to_string(), per_file_selection);
+
+    let input = StandardJsonInput {
+        language: "Solidity",
+        sources,
+        settings: StandardJsonSettings {
+            remappings: remappings.to_vec(),
+            output_selection,
+            stop_after: (stop_after_parsing && supports_stop_after_parsing(version)).then_some("parsing"),
+        },
+    };
+
+    let input_json = serde_json::to_vec(&input).map_err(|err| {
+        VersionCompileError::io(version, files, format!("failed to serialize standard JSON input: {err}"))
+    })?;
+
+    let mut child = Command::new(solc_path)
+        .arg("--standard-json")
+        .current_dir(root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| VersionCompileError::io(version, files, err.to_string()))?;
+
+    // Write stdin on its own thread while this one drains stdout/stderr
+    // below: solc can start writing large ASTs before it's finished
+    // reading its input, and with both sides blocked on a full OS pipe
+    // buffer, writing stdin synchronously before `wait_with_output` would
+    // deadlock forever.
+    let mut stdin = child.stdin.take().expect("solc stdin was piped");
+    let writer = std::thread::spawn(move || stdin.write_all(&input_json));
+
+    let output = child
+        .wait_with_output()
+        .map_err(|err| VersionCompileError::io(version, files, err.to_string()))?;
+
+    writer
+        .join()
+        .map_err(|_| VersionCompileError::io(version, files, "solc stdin writer thread panicked".to_string()))?
+        .map_err(|err| VersionCompileError::io(version, files, format!("failed to write standard JSON input: {err}")))?;
+
+    let response: Value = serde_json::from_slice(&output.stdout).map_err(|err| {
+        VersionCompileError::io(
+            version,
+            files,
+            format!(
+                "failed to parse solc standard-json output: {err} (stderr: {})",
+                String::from_utf8_lossy(&output.stderr)
+            ),
+        )
+    })?;
+
+    let errors: Vec<Value> = response
+        .get("errors")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let has_blocking_error = errors
+        .iter()
+        .any(|error| error.get("severity").and_then(Value::as_str) == Some("error"));
+    if has_blocking_error {
+        return Err(VersionCompileError {
+            version: version.to_string(),
+            files: files.to_vec(),
+            message: format!("solc reported {} compilation error(s)", errors.len()),
+            structured_errors: errors,
+        });
+    }
+
+    let sources_out = response
+        .get("sources")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(sources_out
+        .into_iter()
+        .filter_map(|(path, value)| {
+            let ast = value.get("ast")?.clone();
+            Some((PathBuf::from(path), ast))
+        })
+        .collect())
+}
+
+/// Build the `Project` used to resolve import graphs and solc versions,
+/// honoring `offline`: when set, fail fast with an actionable error listing
+/// every pragma range with no locally installed match, instead of letting
+/// the offline-built project's later network-dependent version resolution
+/// fail with an opaque error.
+fn project_for(
+    root: &Path,
+    remappings: Vec<foundry_compilers::remappings::Remapping>,
+    sources: &BTreeMap<PathBuf, Source>,
+    offline: bool,
+) -> Result<Project, String> {
+    if offline {
+        let missing = missing_solc_versions_for(&pragma_version_reqs(sources));
+        if !missing.is_empty() {
+            return Err(format!(
+                "offline mode: no installed solc version satisfies {} pragma requirement(s): {}",
+                missing.len(),
+                missing.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+            ));
+        }
+    }
+    Ok(get_project_with_options(root, remappings, offline, None))
+}
+
+/// Resolve version groups for `root` and compile them all, merging into one
+/// unified set of AST outputs keyed by solc version, using the default
+/// [`CompileOptions`]. Callers wanting a different batch size, a concurrency cap, or a
+/// fully type-checked AST should use [`compile_multi_version_with_options`]
+/// instead.
+pub fn compile_multi_version(
+    root: &Path,
+    remappings: Vec<foundry_compilers::remappings::Remapping>,
+    raw_remappings: &[String],
+    sources: BTreeMap<PathBuf, Source>,
+) -> Result<BTreeMap<String, Result<BTreeMap<PathBuf, Value>, VersionCompileError>>, String> {
+    compile_multi_version_with_options(root, remappings, raw_remappings, sources, &CompileOptions::default())
+}
+
+/// Like [`compile_multi_version`], but with full control over
+/// [`CompileOptions`].
+pub fn compile_multi_version_with_options(
+    root: &Path,
+    remappings: Vec<foundry_compilers::remappings::Remapping>,
+    raw_remappings: &[String],
+    sources: BTreeMap<PathBuf, Source>,
+    options: &CompileOptions,
+) -> Result<BTreeMap<String, Result<BTreeMap<PathBuf, Value>, VersionCompileError>>, String> {
+    let project = project_for(root, remappings, &sources, options.offline)?;
+    let groups = group_sources_by_version(root, &project, sources)?;
+    ensure_solc_installed(&groups, options.offline).map_err(|err| err.to_string())?;
+    Ok(compile_groups_in_parallel(root, raw_remappings, &groups, options))
+}
+
+/// Like [`compile_multi_version_with_options`], but consults an on-disk
+/// [`CompileCache`] first: a version group is skipped entirely, reusing its
+/// cached ASTs, when every file in it still matches the content hash,
+/// resolved solc version, and remappings it was last compiled under.
+pub fn compile_multi_version_incremental(
+    root: &Path,
+    remappings: Vec<foundry_compilers::remappings::Remapping>,
+    raw_remappings: &[String],
+    sources: BTreeMap<PathBuf, Source>,
+    options: &CompileOptions,
+) -> Result<BTreeMap<String, Result<BTreeMap<PathBuf, Value>, VersionCompileError>>, String> {
+    let project = project_for(root, remappings, &sources, options.offline)?;
+    let groups = group_sources_by_version(root, &project, sources)?;
+    ensure_solc_installed(&groups, options.offline).map_err(|err| err.to_string())?;
+
+    let mut cache = CompileCache::load(root);
+    let remappings_hash = hash_remappings(raw_remappings);
+    let (dirty, mut reused) =
+        partition_dirty_groups(&cache, groups, remappings_hash, options.stop_after_parsing);
+
+    let compiled = compile_groups_in_parallel(root, raw_remappings, &dirty, options);
+
+    let newly_compiled: BTreeMap<String, BTreeMap<PathBuf, Value>> = compiled
+        .iter()
+        .filter_map(|(version, result)| result.as_ref().ok().map(|asts| (version.clone(), asts.clone())))
+        .collect();
+    record_compiled_groups(&mut cache, root, &dirty, remappings_hash, &newly_compiled);
+    cache.save(root);
+
+    // A version can appear in both `reused` (its clean files) and `compiled`
+    // (its dirty ones) once freshness is judged per file, so merge rather
+    // than let one clobber the other.
+    let mut results: BTreeMap<String, Result<BTreeMap<PathBuf, Value>, VersionCompileError>> =
+        BTreeMap::new();
+    for (version, result) in compiled {
+        match result {
+            Ok(asts) => {
+                let mut merged = reused.remove(&version).unwrap_or_default();
+                merged.extend(asts);
+                results.insert(version, Ok(merged));
+            }
+            Err(err) => {
+                // Drop any stale cached ASTs this version had in `reused`:
+                // otherwise the `extend` below would silently resurrect them
+                // and overwrite this `Err`, reporting success with
+                // incomplete data.
+                reused.remove(&version);
+                results.insert(version, Err(err));
+            }
+        }
+    }
+    results.extend(reused.into_iter().map(|(version, asts)| (version, Ok(asts))));
+
+    Ok(results)
+}
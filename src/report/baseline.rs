@@ -0,0 +1,177 @@
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{context::loader::ContextLoader, visitor::ast_visitor::Node};
+
+use super::reporter::{Issue, Report};
+
+/// A content-stable fingerprint for a single issue instance. Built from the
+/// detector name, the normalized source path and a window of source text
+/// around the finding rather than the raw line number, so unrelated edits
+/// that shift lines elsewhere in the file don't resurface old findings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IssueFingerprint(u64);
+
+impl IssueFingerprint {
+    fn new(detector_name: &str, normalized_path: &str, context_window: &str) -> Self {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        detector_name.hash(&mut hasher);
+        normalized_path.hash(&mut hasher);
+        context_window.hash(&mut hasher);
+        IssueFingerprint(hasher.finish())
+    }
+}
+
+/// A previously serialized set of findings, used to suppress issues that
+/// were already known at the time the baseline was captured.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    fingerprints: HashSet<IssueFingerprint>,
+}
+
+impl Baseline {
+    pub fn from_json(contents: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(contents)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Capture every instance currently in `report` as a new baseline.
+    pub fn capture(report: &Report, loader: &ContextLoader) -> Self {
+        let mut fingerprints = HashSet::new();
+        for issue in report
+            .criticals
+            .iter()
+            .chain(&report.highs)
+            .chain(&report.mediums)
+            .chain(&report.lows)
+            .chain(&report.ncs)
+        {
+            for fingerprint in fingerprints_for_issue(issue, loader).into_iter().flatten() {
+                fingerprints.insert(fingerprint);
+            }
+        }
+        Baseline { fingerprints }
+    }
+
+    fn contains(&self, fingerprint: &IssueFingerprint) -> bool {
+        self.fingerprints.contains(fingerprint)
+    }
+}
+
+/// How many findings were dropped because they matched the baseline, versus
+/// how many are newly introduced.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DiffSummary {
+    pub suppressed: usize,
+    pub new: usize,
+}
+
+fn normalize_path(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+fn context_window<T: Node + ?Sized>(
+    loader: &ContextLoader,
+    node: &T,
+    line_number: usize,
+) -> String {
+    let source_unit = loader.get_source_unit_from_child_node(node);
+    let Some(source_unit) = source_unit else {
+        return String::new();
+    };
+    let Some(source) = source_unit.source.as_deref() else {
+        return String::new();
+    };
+
+    const WINDOW: usize = 1;
+    let lines: Vec<&str> = source.lines().collect();
+    let start = line_number.saturating_sub(1 + WINDOW);
+    let end = (line_number + WINDOW).min(lines.len());
+    lines[start..end].join("\n")
+}
+
+/// One fingerprint slot per `issue.instances` entry, `None` wherever the
+/// instance itself is `None` or has no resolvable source unit, so callers can
+/// zip this 1:1 against `issue.instances` without the two sequences drifting
+/// out of sync.
+fn fingerprints_for_issue(issue: &Issue, loader: &ContextLoader) -> Vec<Option<IssueFingerprint>> {
+    issue
+        .instances
+        .iter()
+        .map(|instance| {
+            let node = instance.as_ref()?;
+            let source_unit = loader.get_source_unit_from_child_node(node)?;
+            let path = normalize_path(source_unit.absolute_path.as_deref().unwrap_or("unknown"));
+            let line_number = node
+                .src()
+                .and_then(|src| source_unit.source_line(src).ok())
+                .unwrap_or(0);
+            let window = context_window(loader, node, line_number);
+            Some(IssueFingerprint::new(&issue.name(), &path, &window))
+        })
+        .collect()
+}
+
+/// Drop every instance of `report` that matches a fingerprint already
+/// present in `baseline`, keeping only net-new findings. Issues left with no
+/// surviving instances are dropped entirely.
+pub fn suppress_known_findings(
+    report: Report,
+    loader: &ContextLoader,
+    baseline: &Baseline,
+) -> (Report, DiffSummary) {
+    let mut summary = DiffSummary::default();
+
+    let mut filter_bucket = |issues: Vec<Issue>| -> Vec<Issue> {
+        issues
+            .into_iter()
+            .filter_map(|mut issue| {
+                let fingerprints = fingerprints_for_issue(&issue, loader);
+                let kept: Vec<_> = issue
+                    .instances
+                    .iter()
+                    .cloned()
+                    .zip(fingerprints)
+                    .filter(|(instance, fingerprint)| {
+                        let keep = match fingerprint {
+                            Some(fingerprint) => !baseline.contains(fingerprint),
+                            None => true,
+                        };
+                        if instance.is_some() {
+                            if keep {
+                                summary.new += 1;
+                            } else {
+                                summary.suppressed += 1;
+                            }
+                        }
+                        keep
+                    })
+                    .map(|(instance, _)| instance)
+                    .collect();
+                if kept.is_empty() {
+                    None
+                } else {
+                    issue.instances = kept;
+                    Some(issue)
+                }
+            })
+            .collect()
+    };
+
+    let filtered = Report {
+        criticals: filter_bucket(report.criticals),
+        highs: filter_bucket(report.highs),
+        mediums: filter_bucket(report.mediums),
+        lows: filter_bucket(report.lows),
+        ncs: filter_bucket(report.ncs),
+    };
+
+    (filtered, summary)
+}
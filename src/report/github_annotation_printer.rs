@@ -0,0 +1,120 @@
+use std::io::{Result, Write};
+
+use crate::{ast::SourceUnit, context::loader::ContextLoader};
+
+use super::{
+    printer::ReportPrinter,
+    reporter::{Issue, Report},
+    severity::tier_for_code,
+};
+
+/// Emits GitHub Actions workflow commands so every finding shows up as an
+/// inline annotation on the diff of a pull request.
+pub struct GithubAnnotationReportPrinter;
+
+impl GithubAnnotationReportPrinter {
+    fn command_for(severity: &str) -> &'static str {
+        match tier_for_code(severity) {
+            "note" => "notice",
+            other => other,
+        }
+    }
+
+    /// Escape a value for use as workflow command *data* (the part after
+    /// the final `::`), per
+    /// https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions#escaping-properties-and-data.
+    /// `%` must go first so it doesn't double-escape the `%` introduced by
+    /// the other replacements.
+    fn escape_data(value: &str) -> String {
+        value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+    }
+
+    /// Escape a value for use as a workflow command *property* (a `key=`
+    /// value before the final `::`), which additionally requires escaping
+    /// `:` and `,` since those delimit properties from each other.
+    fn escape_property(value: &str) -> String {
+        Self::escape_data(value).replace(':', "%3A").replace(',', "%2C")
+    }
+
+    fn print_instances<W: Write>(
+        &self,
+        mut writer: W,
+        issue: &Issue,
+        loader: &ContextLoader,
+        severity: &str,
+    ) -> Result<()> {
+        let command = Self::command_for(severity);
+        for node in issue.instances.iter().flatten() {
+            let source_unit: &SourceUnit = loader.get_source_unit_from_child_node(node).unwrap();
+            let contract_path = source_unit.absolute_path.as_deref().unwrap_or("unknown");
+            let mut line_number = 0;
+            if let Some(src) = node.src() {
+                line_number = source_unit.source_line(src).unwrap();
+            }
+            writeln!(
+                writer,
+                "::{} file={},line={},title={}::{}",
+                command,
+                Self::escape_property(contract_path),
+                line_number,
+                Self::escape_property(&issue.name()),
+                Self::escape_data(&issue.description)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl ReportPrinter for GithubAnnotationReportPrinter {
+    fn print_title_and_disclaimer<W: Write>(&self, _writer: W) -> Result<()> {
+        Ok(())
+    }
+
+    fn print_table_of_contents<W: Write>(&self, _writer: W, _report: &Report) -> Result<()> {
+        Ok(())
+    }
+
+    fn print_contract_summary<W: Write>(
+        &self,
+        _writer: W,
+        _report: &Report,
+        _loader: &ContextLoader,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn print_issue<W: Write>(
+        &self,
+        writer: W,
+        issue: &Issue,
+        loader: &ContextLoader,
+        severity: &str,
+        _number: i32,
+    ) -> Result<()> {
+        self.print_instances(writer, issue, loader, severity)
+    }
+
+    fn print_report<W: Write>(
+        &self,
+        mut writer: W,
+        report: &Report,
+        loader: &ContextLoader,
+    ) -> Result<()> {
+        for issue in &report.criticals {
+            self.print_issue(&mut writer, issue, loader, "C", 0)?;
+        }
+        for issue in &report.highs {
+            self.print_issue(&mut writer, issue, loader, "H", 0)?;
+        }
+        for issue in &report.mediums {
+            self.print_issue(&mut writer, issue, loader, "M", 0)?;
+        }
+        for issue in &report.lows {
+            self.print_issue(&mut writer, issue, loader, "L", 0)?;
+        }
+        for issue in &report.ncs {
+            self.print_issue(&mut writer, issue, loader, "NC", 0)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+/// Collapse a detector's single-letter severity code into the three-tier
+/// bucket every printer in this module displays: a blocking `"error"`, an
+/// advisory `"warning"`, or an informational `"note"`/`"notice"`. `"L"`
+/// lands alongside `"NC"` rather than being escalated to a warning, matching
+/// `aderyn_core`'s `sarif_level` treatment of `IssueSeverity::Low`. Shared by
+/// [`super::snippet_printer`] and [`super::github_annotation_printer`] so
+/// the two agree on every tier.
+pub(super) fn tier_for_code(severity: &str) -> &'static str {
+    match severity {
+        "C" | "H" => "error",
+        "M" => "warning",
+        _ => "note",
+    }
+}
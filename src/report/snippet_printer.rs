@@ -0,0 +1,129 @@
+use std::io::{Result, Write};
+
+use annotate_snippets::{Level, Renderer, Snippet};
+
+use crate::{ast::SourceUnit, context::loader::ContextLoader};
+
+use super::{
+    printer::ReportPrinter,
+    reporter::{Issue, Report},
+    severity::tier_for_code,
+};
+
+pub struct SnippetReportPrinter;
+
+impl SnippetReportPrinter {
+    fn level_for(severity: &str) -> Level {
+        match tier_for_code(severity) {
+            "error" => Level::Error,
+            "warning" => Level::Warning,
+            _ => Level::Note,
+        }
+    }
+
+    fn print_instances<W: Write>(
+        &self,
+        mut writer: W,
+        issue: &Issue,
+        loader: &ContextLoader,
+        severity: &str,
+    ) -> Result<()> {
+        let level = Self::level_for(severity);
+        for node in issue.instances.iter().flatten() {
+            let source_unit: &SourceUnit = loader.get_source_unit_from_child_node(node).unwrap();
+            let contract_path = source_unit.absolute_path.as_deref().unwrap_or("unknown");
+            let Some(src) = node.src() else {
+                continue;
+            };
+            let Some(source) = source_unit.source.as_deref() else {
+                continue;
+            };
+
+            let parts: Vec<&str> = src.split(':').collect();
+            let start: usize = parts[0].parse().unwrap_or(0);
+            let length: usize = parts.get(1).and_then(|p| p.parse().ok()).unwrap_or(0);
+            let end = start + length;
+
+            // Widen the slice to the full lines the span touches, so the
+            // caret lands under the right tokens instead of a raw byte range.
+            let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = source[end..]
+                .find('\n')
+                .map_or(source.len(), |i| end + i);
+
+            let annotation_start = start - line_start;
+            let annotation_end = end - line_start;
+
+            let message = level.title(&issue.title).snippet(
+                Snippet::source(&source[line_start..line_end])
+                    .origin(contract_path)
+                    .fold(true)
+                    .annotation(
+                        level
+                            .span(annotation_start..annotation_end)
+                            .label(&issue.description),
+                    ),
+            );
+
+            let renderer = Renderer::styled();
+            writeln!(writer, "{}", renderer.render(message))?;
+        }
+        Ok(())
+    }
+}
+
+impl ReportPrinter for SnippetReportPrinter {
+    fn print_title_and_disclaimer<W: Write>(&self, mut writer: W) -> Result<()> {
+        writeln!(writer, "Aderyn Analysis Report\n")?;
+        Ok(())
+    }
+
+    fn print_table_of_contents<W: Write>(&self, _writer: W, _report: &Report) -> Result<()> {
+        Ok(())
+    }
+
+    fn print_contract_summary<W: Write>(
+        &self,
+        _writer: W,
+        _report: &Report,
+        _loader: &ContextLoader,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn print_issue<W: Write>(
+        &self,
+        writer: W,
+        issue: &Issue,
+        loader: &ContextLoader,
+        severity: &str,
+        _number: i32,
+    ) -> Result<()> {
+        self.print_instances(writer, issue, loader, severity)
+    }
+
+    fn print_report<W: Write>(
+        &self,
+        mut writer: W,
+        report: &Report,
+        loader: &ContextLoader,
+    ) -> Result<()> {
+        self.print_title_and_disclaimer(&mut writer)?;
+        for issue in &report.criticals {
+            self.print_issue(&mut writer, issue, loader, "C", 0)?;
+        }
+        for issue in &report.highs {
+            self.print_issue(&mut writer, issue, loader, "H", 0)?;
+        }
+        for issue in &report.mediums {
+            self.print_issue(&mut writer, issue, loader, "M", 0)?;
+        }
+        for issue in &report.lows {
+            self.print_issue(&mut writer, issue, loader, "L", 0)?;
+        }
+        for issue in &report.ncs {
+            self.print_issue(&mut writer, issue, loader, "NC", 0)?;
+        }
+        Ok(())
+    }
+}